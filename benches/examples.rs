@@ -0,0 +1,74 @@
+//!
+//! Benchmark of the interpreter running the fibonacci and factorial examples at scale.
+//! Output of the examples is left out, so only the time spent interpreting is measured.
+//!
+//! Run with `cargo bench`.
+//!
+
+use std::time::{ Duration, Instant };
+use runk::Interpreter;
+
+/// Loop of the fibonacci example going up to the 20000th number.
+const FIBONACCI: &str = "\
+Nat max: 20000
+Int n1: 1
+Int n2: 0
+Int helper: 0
+Nat order: 1
+
+!loop
+helper: $n1
+n1: (+ $n1 $n2)
+n2: $helper
+order: (+ $order 1)
+(goif (<= $order $max) !loop)
+";
+
+/// Loop of the factorial example calculating the factorial of 20 for 5000 times.
+const FACTORIAL: &str = "\
+Nat runs: 5000
+
+!input
+Nat n: 20
+Nat fact: 1
+
+!loop
+fact: (* $fact $n)
+n: (- $n 1)
+(goif (> $n 0) !loop)
+
+runs: (- $runs 1)
+(goif (> $runs 0) !input)
+";
+
+/// Same as `FACTORIAL` but written with blocks and infix expressions.
+const FACTORIAL_BLOCKS: &str = "\
+Nat runs: 5000
+<while [$runs > 0]
+    Nat n: 20
+    Nat fact: 1
+    <while [$n > 0]
+        fact: [$fact * $n]
+        n: [$n - 1]
+    >
+    runs: [$runs - 1]
+>
+";
+
+/// Runs `source` in a fresh interpreter `samples` times and returns the fastest run.
+fn measure(source: &str, samples: usize) -> Duration {
+    (0..samples).map(|_| {
+        let mut interpreter = Interpreter::new();
+        interpreter.set_prescan_lables(true);
+        let start = Instant::now();
+        interpreter.run_str(source).unwrap();
+        start.elapsed()
+    }).min().unwrap()
+}
+
+fn main() {
+    for (name, source) in [("fibonacci", FIBONACCI), ("factorial", FACTORIAL), ("factorial_blocks", FACTORIAL_BLOCKS)] {
+        let time = measure(source, 5);
+        println!("{:<20} {:>10.2} ms", name, time.as_secs_f64() * 1000.0);
+    }
+}
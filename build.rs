@@ -0,0 +1,20 @@
+// The header of the C interface is written into the source tree, so it's only generated
+// when asked for with `cargo build --features header`. CI checks it is up to date.
+#[cfg(feature = "header")]
+fn main() {
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    match cbindgen::generate(&crate_dir) {
+        Ok(bindings) => {
+            bindings.write_to_file("include/runk.h");
+        },
+        Err(e) => println!("cargo:warning=Unable to generate include/runk.h: {}", e),
+    };
+}
+
+#[cfg(not(feature = "header"))]
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+}
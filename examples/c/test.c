@@ -0,0 +1,73 @@
+/*
+ * Tests of the C interface. Build runk first and then run this from the project root:
+ *
+ *     cc -o target/c-test examples/c/test.c -Iinclude -Ltarget/debug -lrunk -lpthread -ldl -lm
+ *     ./target/c-test
+ */
+
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+#include "runk.h"
+
+#define CHECK(condition) \
+    if (!(condition)) { \
+        fprintf(stderr, "%s:%d: Check failed: %s\n", __FILE__, __LINE__, #condition); \
+        return 1; \
+    }
+
+int main(void) {
+    RunkInterpreter *runk = runk_new();
+    int64_t number = 0;
+    char *text = NULL;
+
+    /* Variables set from C can be used by runk code and the other way around. */
+    CHECK(runk_set_int(runk, "x", 20) == RUNK_STATUS_OK);
+    CHECK(runk_set_text(runk, "name", "Ronald") == RUNK_STATUS_OK);
+    CHECK(runk_run_string(runk,
+        "<fn Int add Int a Int b\n"
+        "    ret [$a + $b]\n"
+        ">\n"
+        "Int y: (add $x 1)\n"
+        "Txt greeting: \"Hi $name!\"\n") == RUNK_STATUS_OK);
+    CHECK(runk_last_error(runk) == NULL);
+
+    CHECK(runk_get_int(runk, "y", &number) == RUNK_STATUS_OK);
+    CHECK(number == 21);
+
+    text = runk_get_text(runk, "greeting");
+    CHECK(text != NULL);
+    CHECK(strcmp(text, "Hi Ronald!") == 0);
+    runk_string_free(text);
+
+    /* Wrong use of the interface. */
+    CHECK(runk_get_int(runk, "greeting", &number) == RUNK_STATUS_INVALID_ARGUMENT);
+    CHECK(runk_get_int(runk, "missing", &number) == RUNK_STATUS_INVALID_ARGUMENT);
+    CHECK(runk_get_int(runk, "y", NULL) == RUNK_STATUS_INVALID_ARGUMENT);
+    CHECK(strstr(runk_last_error(runk), "null") != NULL);
+    CHECK(runk_get_text(runk, "missing") == NULL);
+    CHECK(runk_run_string(runk, NULL) == RUNK_STATUS_INVALID_ARGUMENT);
+
+    /* Errors are reported and don't end the program. */
+    CHECK(runk_run_string(runk, "Nat z: (nope)\n") == RUNK_STATUS_RUNTIME_ERROR);
+    CHECK(strstr(runk_last_error(runk), "nope") != NULL);
+    CHECK(runk_run_string(runk, "<if 1\n") == RUNK_STATUS_SYNTAX_ERROR);
+    CHECK(runk_run_string(runk, "\"open\n") == RUNK_STATUS_PARSE_ERROR);
+    CHECK(runk_run_string(runk, "(exit 3)\n") == RUNK_STATUS_EXIT);
+    CHECK(runk_exit_code(runk) == 3);
+
+    /* Code can be read from a file descriptor. */
+    int fds[2];
+    const char *source = "Int w: [$y * 2]\n";
+    CHECK(pipe(fds) == 0);
+    CHECK(write(fds[1], source, strlen(source)) == (ssize_t) strlen(source));
+    close(fds[1]);
+    CHECK(runk_run_fd(runk, fds[0]) == RUNK_STATUS_OK);
+    close(fds[0]);
+    CHECK(runk_get_int(runk, "w", &number) == RUNK_STATUS_OK);
+    CHECK(number == 42);
+
+    runk_free(runk);
+    printf("All C interface tests passed.\n");
+    return 0;
+}
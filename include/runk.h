@@ -0,0 +1,93 @@
+#ifndef RUNK_H
+#define RUNK_H
+
+/* Generated by cbindgen from src/capi.rs by `cargo build --features header`, do not edit. */
+
+#include <stdint.h>
+
+// Result of a call to the runk C interface.
+typedef enum RunkStatus {
+  RUNK_STATUS_OK = 0,
+  RUNK_STATUS_PARSE_ERROR,
+  RUNK_STATUS_SYNTAX_ERROR,
+  RUNK_STATUS_RUNTIME_ERROR,
+  // The program called "exit", its code can be read with `runk_exit_code`.
+  RUNK_STATUS_EXIT,
+  // Arguments of the call were wrong, like a null pointer or a variable that isn't defined.
+  RUNK_STATUS_INVALID_ARGUMENT,
+} RunkStatus;
+
+// Interpreter together with the outcome of the last call made on it.
+typedef struct RunkInterpreter RunkInterpreter;
+
+// Creates a new interpreter. It has to be destroyed with `runk_free`.
+struct RunkInterpreter *runk_new(void);
+
+// Destroys an interpreter created with `runk_new`.
+//
+// # Safety
+// `runk` has to be null or come from `runk_new` and can't be used afterwards.
+void runk_free(struct RunkInterpreter *runk);
+
+// Runs runk source code. Variables and functions are kept for later runs.
+//
+// # Safety
+// `runk` has to come from `runk_new` and `source` has to be a null terminated string.
+enum RunkStatus runk_run_string(struct RunkInterpreter *runk, const char *source);
+
+#if defined(__unix__)
+// Runs runk source code read from a file descriptor until its end. The descriptor
+// is left open.
+//
+// # Safety
+// `runk` has to come from `runk_new` and `fd` has to be an open file descriptor.
+enum RunkStatus runk_run_fd(struct RunkInterpreter *runk, int fd);
+#endif
+
+// Defines a variable holding an integer or replaces the value of an existing one.
+//
+// # Safety
+// `runk` has to come from `runk_new` and `name` has to be a null terminated string.
+enum RunkStatus runk_set_int(struct RunkInterpreter *runk, const char *name, int64_t value);
+
+// Defines a variable holding text or replaces the value of an existing one.
+//
+// # Safety
+// `runk` has to come from `runk_new`, `name` and `value` have to be null terminated strings.
+enum RunkStatus runk_set_text(struct RunkInterpreter *runk, const char *name, const char *value);
+
+// Reads a variable holding a number into `out`. Fails if the variable isn't defined,
+// isn't a number or doesn't fit into 64 bits.
+//
+// # Safety
+// `runk` has to come from `runk_new`, `name` has to be a null terminated string and
+// `out` has to point to writable memory.
+enum RunkStatus runk_get_int(struct RunkInterpreter *runk, const char *name, int64_t *out);
+
+// Returns the value of any variable as text or null if it isn't defined. The text
+// has to be freed with `runk_string_free`.
+//
+// # Safety
+// `runk` has to come from `runk_new` and `name` has to be a null terminated string.
+char *runk_get_text(struct RunkInterpreter *runk, const char *name);
+
+// Frees text returned by `runk_get_text`.
+//
+// # Safety
+// `string` has to be null or come from `runk_get_text` and can't be used afterwards.
+void runk_string_free(char *string);
+
+// Returns description of the error from the last call or null if it succeeded. The
+// text belongs to the interpreter and is valid until the next call.
+//
+// # Safety
+// `runk` has to come from `runk_new`.
+const char *runk_last_error(const struct RunkInterpreter *runk);
+
+// Returns the code the program passed to "exit" the last time it was called.
+//
+// # Safety
+// `runk` has to come from `runk_new`.
+int runk_exit_code(const struct RunkInterpreter *runk);
+
+#endif /* RUNK_H */
@@ -0,0 +1,167 @@
+//!
+//! Compiles syntax trees of lines into bytecode run by the stack machine in `expressions`.
+//! Names of variables and functions are resolved to slots during the compilation.
+//!
+
+use crate::structs::{ var, word, assign, program_data };
+use crate::parser::ast;
+
+/// Instruction of the stack machine.
+#[derive(Debug, Clone)]
+pub enum Op {
+    /// Pushes a value written in the code.
+    Push(var::Var),
+    /// Pushes the value of the variable in a slot. Second number is an index into `Chunk::words`.
+    Load(usize, usize),
+    /// Calls the function in a slot with the given amount of arguments from the top of the stack.
+    /// Third number is an index into `Chunk::words`.
+    Call(usize, usize, usize),
+    /// Joins the given amount of values from the top of the stack into text.
+    Concat(usize),
+    /// Collects the given amount of values from the top of the stack into a list.
+    List(usize),
+    /// Collects the given amount of pairs of keys and values from the top of the stack into a map.
+    /// Second number is an index into `Chunk::words`.
+    Map(usize, usize),
+    /// Starts a call that has an alternative. If anything fails before the matching `EndTry`,
+    /// the values pushed since then are dropped and the code continues at the given index.
+    Try(usize),
+    /// Ends a call that didn't fail and skips its alternative by continuing at the given index.
+    EndTry(usize),
+    /// Stops the code and requests a jump to a lable.
+    JumpLable(String),
+}
+
+/// Compiled expression of a line.
+#[derive(Debug, Clone, Default)]
+pub struct Chunk {
+    pub ops: Vec<Op>,
+    /// Words of the line that instructions refer to when something fails.
+    pub words: Vec<word::Word>,
+    /// Slot of the variable the value of the expression is assigned to.
+    pub target: Option<usize>,
+}
+
+impl Chunk {
+    /// # Description
+    /// Compiles the expression of a statement, statements without one result in an empty chunk.
+    ///
+    /// # Arguments
+    /// - `statement`: Statement to compile.
+    /// - `data`: Runtime data of the runk program, slots are assigned in it.
+    pub fn new(statement: &ast::Statement, data: &mut program_data::ProgramData) -> Chunk {
+        let mut chunk = Chunk::default();
+        if let ast::Statement::Expression(Some(assign), _) = statement {
+            chunk.target = Some(match assign {
+                assign::Assign::Dec(_, name) | assign::Assign::Const(_, name) | assign::Assign::Nondec(name) => data.vars.slot(name),
+            });
+        }
+        if let Some(expression) = statement.expression() {
+            chunk.compile_expression(expression, data);
+        }
+
+        chunk
+    }
+
+    /// Stores `word` and returns its index.
+    fn add_word(&mut self, word: &word::Word) -> usize {
+        self.words.push(word.clone());
+        self.words.len() - 1
+    }
+
+    fn compile_expression(&mut self, expression: &ast::Expression, data: &mut program_data::ProgramData) {
+        match expression {
+            ast::Expression::Literal(value, _) => self.ops.push(Op::Push(value.clone())),
+            ast::Expression::Variable(name, word) => {
+                let word = self.add_word(word);
+                self.ops.push(Op::Load(data.vars.slot(name), word));
+            },
+            ast::Expression::Call(call) => self.compile_call(call, data),
+            ast::Expression::Text(parts) => {
+                for part in parts {
+                    match part {
+                        ast::TextPart::Text(text) => self.ops.push(Op::Push(var::Var::T(text.clone()))),
+                        ast::TextPart::Expression(expression) => self.compile_expression(expression, data),
+                    }
+                }
+                self.ops.push(Op::Concat(parts.len()));
+            },
+            ast::Expression::List(items) => {
+                for item in items {
+                    self.compile_expression(item, data);
+                }
+                self.ops.push(Op::List(items.len()));
+            },
+            ast::Expression::Map(pairs, word) => {
+                for (key, value) in pairs {
+                    self.compile_expression(key, data);
+                    self.compile_expression(value, data);
+                }
+                let word = self.add_word(word);
+                self.ops.push(Op::Map(pairs.len(), word));
+            },
+        }
+    }
+
+    /// Compiles a call and its alternative like this:
+    ///
+    /// ```text
+    ///     Try handler    (only with an alternative)
+    ///     ...arguments
+    ///     Call
+    ///     EndTry end
+    /// handler:
+    ///     ...alternative
+    /// end:
+    /// ```
+    fn compile_call(&mut self, call: &ast::Call, data: &mut program_data::ProgramData) {
+        let try_index = self.ops.len();
+        if call.on_fail.is_some() {
+            // Target is filled in once the alternative's position is known.
+            self.ops.push(Op::Try(0));
+        }
+
+        for arg in &call.args {
+            self.compile_expression(arg, data);
+        }
+        let word = self.add_word(&call.function);
+        self.ops.push(Op::Call(data.funcs.slot(call.name()), call.args.len(), word));
+
+        let on_fail = match call.on_fail.as_deref() {
+            Some(on_fail) => on_fail,
+            None => return,
+        };
+        let end_try_index = self.ops.len();
+        self.ops.push(Op::EndTry(0));
+        self.ops[try_index] = Op::Try(self.ops.len());
+
+        match on_fail {
+            ast::OnFail::Call(replacement) => self.compile_call(replacement, data),
+            ast::OnFail::Jump(lable, _) => self.ops.push(Op::JumpLable(lable.clone())),
+        }
+        self.ops[end_try_index] = Op::EndTry(self.ops.len());
+    }
+}
+
+
+#[test]
+fn test_compile_call() {
+    let mut data = program_data::ProgramData::new(false);
+    let line = match crate::parser::parse_file(&mut std::io::Cursor::new("Nat a: (/ $x 0) else !retry\n"), "", 0, false) {
+        crate::parser::ParseResult::Ok(line, _) => line,
+        _ => panic!(),
+    };
+    let chunk = Chunk::new(&line.statement, &mut data);
+
+    let x = data.vars.find("x").unwrap();
+    let div = data.funcs.find("/").unwrap();
+    assert_eq!(chunk.target, data.vars.find("a"));
+    assert!(matches!(&chunk.ops[..], [
+        Op::Try(5),
+        Op::Load(slot, 0),
+        Op::Push(_),
+        Op::Call(f, 2, 1),
+        Op::EndTry(6),
+        Op::JumpLable(l),
+    ] if *slot == x && *f == div && l == "retry"));
+}
@@ -0,0 +1,328 @@
+//!
+//! C interface for embedding runk into C and C++ programs. The header with these
+//! declarations is generated into `include/runk.h` by `cargo build --features header`.
+//!
+//! All strings are expected to be null terminated and encoded in UTF-8.
+//!
+
+use std::ffi::{ CStr, CString };
+use std::os::raw::{ c_char, c_int };
+use std::ptr;
+use num_traits::ToPrimitive;
+
+use crate::Interpreter;
+use crate::structs::var;
+use crate::structs::runk_error::{ RunkError, ErrorKind };
+
+/// Result of a call to the runk C interface.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RunkStatus {
+    Ok = 0,
+    ParseError,
+    SyntaxError,
+    RuntimeError,
+    /// The program called "exit", its code can be read with `runk_exit_code`.
+    Exit,
+    /// Arguments of the call were wrong, like a null pointer or a variable that isn't defined.
+    InvalidArgument,
+}
+
+/// Interpreter together with the outcome of the last call made on it.
+pub struct RunkInterpreter {
+    interpreter: Interpreter,
+    last_error: Option<CString>,
+    exit_code: c_int,
+}
+
+impl RunkInterpreter {
+    /// Remembers the outcome of a call and converts it to a status.
+    fn finish(&mut self, result: Result<(), RunkError>) -> RunkStatus {
+        let error = match result {
+            Ok(_) => {
+                self.last_error = None;
+                return RunkStatus::Ok;
+            },
+            Err(e) => e,
+        };
+
+        self.last_error = CString::new(error.to_string().replace('\0', "")).ok();
+        match error.kind {
+            ErrorKind::Parse => RunkStatus::ParseError,
+            ErrorKind::Syntax => RunkStatus::SyntaxError,
+            ErrorKind::Runtime => RunkStatus::RuntimeError,
+            ErrorKind::Exit(code) => {
+                self.exit_code = code;
+                RunkStatus::Exit
+            },
+        }
+    }
+
+    /// Records a problem with arguments of a call.
+    fn invalid_argument(&mut self, message: String) -> RunkStatus {
+        self.last_error = CString::new(message).ok();
+        RunkStatus::InvalidArgument
+    }
+}
+
+/// Reads a string from C.
+///
+/// # Safety
+/// `string` has to be null or point to a null terminated string.
+unsafe fn read_string<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
+
+/// Creates a new interpreter. It has to be destroyed with `runk_free`.
+#[no_mangle]
+pub extern "C" fn runk_new() -> *mut RunkInterpreter {
+    Box::into_raw(Box::new(RunkInterpreter {
+        interpreter: Interpreter::new(),
+        last_error: None,
+        exit_code: 0,
+    }))
+}
+
+/// Destroys an interpreter created with `runk_new`.
+///
+/// # Safety
+/// `runk` has to be null or come from `runk_new` and can't be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn runk_free(runk: *mut RunkInterpreter) {
+    if !runk.is_null() {
+        drop(Box::from_raw(runk));
+    }
+}
+
+/// Runs runk source code. Variables and functions are kept for later runs.
+///
+/// # Safety
+/// `runk` has to come from `runk_new` and `source` has to be a null terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn runk_run_string(runk: *mut RunkInterpreter, source: *const c_char) -> RunkStatus {
+    let runk = &mut *runk;
+    let source = match read_string(source) {
+        Some(s) => s,
+        None => return runk.invalid_argument("Source code is not a valid string!".to_string()),
+    };
+
+    let result = runk.interpreter.run_str(source);
+    runk.finish(result)
+}
+
+/// Runs runk source code read from a file descriptor until its end. The descriptor
+/// is left open.
+///
+/// # Safety
+/// `runk` has to come from `runk_new` and `fd` has to be an open file descriptor.
+#[cfg(target_family = "unix")]
+#[no_mangle]
+pub unsafe extern "C" fn runk_run_fd(runk: *mut RunkInterpreter, fd: c_int) -> RunkStatus {
+    use std::os::unix::io::FromRawFd;
+    use std::io::BufReader;
+    use std::mem::ManuallyDrop;
+
+    let runk = &mut *runk;
+    // The file isn't ours to close.
+    let file = ManuallyDrop::new(std::fs::File::from_raw_fd(fd));
+    let mut reader = BufReader::new(&*file);
+    let result = runk.interpreter.run_reader(&mut reader, &format!("<fd {}>", fd), false);
+    runk.finish(result)
+}
+
+/// Defines a variable holding an integer or replaces the value of an existing one.
+///
+/// # Safety
+/// `runk` has to come from `runk_new` and `name` has to be a null terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn runk_set_int(runk: *mut RunkInterpreter, name: *const c_char, value: i64) -> RunkStatus {
+    let runk = &mut *runk;
+    let name = match read_string(name) {
+        Some(s) => s,
+        None => return runk.invalid_argument("Variable name is not a valid string!".to_string()),
+    };
+
+    runk.interpreter.set_var(name, var::Var::new(value));
+    runk.finish(Ok(()))
+}
+
+/// Defines a variable holding text or replaces the value of an existing one.
+///
+/// # Safety
+/// `runk` has to come from `runk_new`, `name` and `value` have to be null terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn runk_set_text(runk: *mut RunkInterpreter, name: *const c_char, value: *const c_char) -> RunkStatus {
+    let runk = &mut *runk;
+    let (name, value) = match (read_string(name), read_string(value)) {
+        (Some(n), Some(v)) => (n, v),
+        _ => return runk.invalid_argument("Variable name or value is not a valid string!".to_string()),
+    };
+
+    runk.interpreter.set_var(name, var::Var::T(value.to_string()));
+    runk.finish(Ok(()))
+}
+
+/// Reads a variable holding a number into `out`. Fails if the variable isn't defined,
+/// isn't a number or doesn't fit into 64 bits.
+///
+/// # Safety
+/// `runk` has to come from `runk_new`, `name` has to be a null terminated string and
+/// `out` has to point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn runk_get_int(runk: *mut RunkInterpreter, name: *const c_char, out: *mut i64) -> RunkStatus {
+    let runk = &mut *runk;
+    let name = match read_string(name) {
+        Some(s) => s,
+        None => return runk.invalid_argument("Variable name is not a valid string!".to_string()),
+    };
+    if out.is_null() {
+        return runk.invalid_argument(format!("Pointer to store variable \"{}\" in is null!", name));
+    }
+
+    let value = match runk.interpreter.get_var(name) {
+        Some(var::Var::N(n)) | Some(var::Var::Z(n)) => n.to_i64(),
+        Some(_) => return runk.invalid_argument(format!("Variable \"{}\" is not a number!", name)),
+        None => return runk.invalid_argument(format!("Variable \"{}\" was not found.", name)),
+    };
+
+    match value {
+        Some(v) => {
+            *out = v;
+            runk.finish(Ok(()))
+        },
+        None => runk.invalid_argument(format!("Variable \"{}\" doesn't fit into 64 bits!", name)),
+    }
+}
+
+/// Returns the value of any variable as text or null if it isn't defined. The text
+/// has to be freed with `runk_string_free`.
+///
+/// # Safety
+/// `runk` has to come from `runk_new` and `name` has to be a null terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn runk_get_text(runk: *mut RunkInterpreter, name: *const c_char) -> *mut c_char {
+    let runk = &mut *runk;
+    let name = match read_string(name) {
+        Some(s) => s,
+        None => {
+            runk.invalid_argument("Variable name is not a valid string!".to_string());
+            return ptr::null_mut();
+        },
+    };
+
+    let text = match runk.interpreter.get_var(name) {
+        Some(v) => v.plain_string(),
+        None => {
+            runk.invalid_argument(format!("Variable \"{}\" was not found.", name));
+            return ptr::null_mut();
+        },
+    };
+
+    runk.finish(Ok(()));
+    match CString::new(text.replace('\0', "")) {
+        Ok(s) => s.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Frees text returned by `runk_get_text`.
+///
+/// # Safety
+/// `string` has to be null or come from `runk_get_text` and can't be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn runk_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Returns description of the error from the last call or null if it succeeded. The
+/// text belongs to the interpreter and is valid until the next call.
+///
+/// # Safety
+/// `runk` has to come from `runk_new`.
+#[no_mangle]
+pub unsafe extern "C" fn runk_last_error(runk: *const RunkInterpreter) -> *const c_char {
+    match &(*runk).last_error {
+        Some(s) => s.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// Returns the code the program passed to "exit" the last time it was called.
+///
+/// # Safety
+/// `runk` has to come from `runk_new`.
+#[no_mangle]
+pub unsafe extern "C" fn runk_exit_code(runk: *const RunkInterpreter) -> c_int {
+    (*runk).exit_code
+}
+
+
+// ==========
+// Unit tests
+// ==========
+#[cfg(test)]
+unsafe fn last_error(runk: *const RunkInterpreter) -> Option<String> {
+    read_string(runk_last_error(runk)).map(|s| s.to_string())
+}
+
+#[test]
+// C string literals would be clearer, but cbindgen can't parse them.
+#[allow(clippy::manual_c_str_literals)]
+fn test_c_interface() {
+    unsafe {
+        let runk = runk_new();
+        assert_eq!(runk_set_int(runk, b"x\0".as_ptr().cast(), 20), RunkStatus::Ok);
+        assert_eq!(runk_set_text(runk, b"name\0".as_ptr().cast(), b"Ronald\0".as_ptr().cast()), RunkStatus::Ok);
+        assert_eq!(runk_run_string(runk, b"Int y: [$x + 1]\nTxt hi: \"Hi $name!\"\n\0".as_ptr().cast()), RunkStatus::Ok);
+        assert_eq!(last_error(runk), None);
+
+        let mut number = 0;
+        assert_eq!(runk_get_int(runk, b"y\0".as_ptr().cast(), &mut number), RunkStatus::Ok);
+        assert_eq!(number, 21);
+        let text = runk_get_text(runk, b"hi\0".as_ptr().cast());
+        assert_eq!(read_string(text), Some("Hi Ronald!"));
+        runk_string_free(text);
+        runk_free(runk);
+    }
+}
+
+#[test]
+#[allow(clippy::manual_c_str_literals)]
+fn test_c_interface_errors() {
+    unsafe {
+        let runk = runk_new();
+        let mut number = 0;
+        assert_eq!(runk_run_string(runk, b"Nat z: (nope)\n\0".as_ptr().cast()), RunkStatus::RuntimeError);
+        assert!(last_error(runk).unwrap().contains("nope"));
+        assert_eq!(runk_run_string(runk, b"<if 1\n\0".as_ptr().cast()), RunkStatus::SyntaxError);
+        assert_eq!(runk_run_string(runk, b"Nat a: (+ 1\n\0".as_ptr().cast()), RunkStatus::ParseError);
+        assert_eq!(runk_run_string(runk, b"(exit 3)\n\0".as_ptr().cast()), RunkStatus::Exit);
+        assert_eq!(runk_exit_code(runk), 3);
+
+        // Null pointers and missing or wrong variables.
+        assert_eq!(runk_run_string(runk, ptr::null()), RunkStatus::InvalidArgument);
+        assert_eq!(runk_get_int(runk, ptr::null(), &mut number), RunkStatus::InvalidArgument);
+        assert!(last_error(runk).unwrap().contains("name"));
+        assert_eq!(runk_run_string(runk, b"Txt t: \"a\"\n\0".as_ptr().cast()), RunkStatus::Ok);
+        assert_eq!(runk_get_int(runk, b"t\0".as_ptr().cast(), ptr::null_mut()), RunkStatus::InvalidArgument);
+        assert!(last_error(runk).unwrap().contains("null"));
+        assert_eq!(runk_get_int(runk, b"t\0".as_ptr().cast(), &mut number), RunkStatus::InvalidArgument);
+        assert_eq!(runk_get_int(runk, b"missing\0".as_ptr().cast(), &mut number), RunkStatus::InvalidArgument);
+        assert!(runk_get_text(runk, b"missing\0".as_ptr().cast()).is_null());
+        assert_eq!(runk_set_text(runk, b"u\0".as_ptr().cast(), ptr::null()), RunkStatus::InvalidArgument);
+        assert_eq!(runk_run_string(runk, b"Nat big: 18446744073709551616\n\0".as_ptr().cast()), RunkStatus::Ok);
+        assert_eq!(runk_get_int(runk, b"big\0".as_ptr().cast(), &mut number), RunkStatus::InvalidArgument);
+        assert!(last_error(runk).unwrap().contains("64 bits"));
+
+        // Successful call clears the error.
+        assert_eq!(runk_run_string(runk, b"Nat b: 1\n\0".as_ptr().cast()), RunkStatus::Ok);
+        assert!(runk_last_error(runk).is_null());
+        runk_free(runk);
+        runk_free(ptr::null_mut());
+    }
+}
@@ -2,73 +2,40 @@
 //! Collection of functions for resolving runk expressions.
 //!
 
-use crate::structs::{var, program_data, word, source_info};
+use crate::structs::{var, program_data, word};
+use crate::structs::runk_error::RunkError;
 use num_traits::Zero;
 use crate::structs::func::{ self, func_return };
 use crate::parser::rtoken;
+use crate::bytecode;
 
 //use structs::{var::Var, assign::Assign, program_data::ProgramData, word::Word, line::Line};
 
-use crate::prints::*;
+use colored::Colorize;
 
 
 /// # Description
-/// Finds a `OnFunctionFail` rtoken and call a replacement function or a lable to jump to.
+/// Calls a runk function by its name.
 ///
-/// # Args
-/// - `input`: Word array cropped from the left to the expected begining of the expression
-///            no cropping from the right though.
-/// - `info`: Info about the source that is beeing processed.
+/// # Arguments
+/// - `operation`: Word containing the function name.
+/// - `operands`: Vector of Words supplied as arguments to the function.
 /// - `data`: Internal state of the program.
 ///
 /// # Returns
-/// - `Some`: Replacement function or lable was found.
-///     - `FuncReturn`: Struct with information souch as result of the expression, return value and a request to jump to a lable.
-///     - `usize`: Index of last Word in `input` relevant to the expression processing.
-/// - `None`: No `OnFunctionFail` rtoken was found
-fn try_replacement_function(input: &[word::Word],
-                            info: &source_info::SourceInfo,
-                            data: &mut program_data::ProgramData) -> Option<(func_return::FuncReturn, usize)> {
-    let mut i: usize = 0;
-    let mut found_on_fail_token = false;
-
-    // Find on function fail token.
-    while i != input.len() {
-        if let rtoken::Rtoken::OnFunctionFail = input[i].rtoken {
-            found_on_fail_token = true;
-            i += 1;
-            break;
-        }
-        i += 1;
-    }
-
-    if !found_on_fail_token {
-        return None;
-    }
-
-    // Check length.
-    if input.len() <= i {
-        fatal_error(&info, format!("No lable literal or a function call after function onfail token"), Some(&input[input.len()-1]));
+/// - `Ok`: Struct with information souch as result of the expression, return value and a request to jump to a lable.
+///   Failure of the function is stored inside of it, so it can be handled with a replacement function.
+/// - `Err`: Error that stops the program, like one inside the body of a function defined in runk.
+pub fn execute_function(operation: &word::Word,
+                    operands: &mut Vec<var::Var>,
+                    data: &mut program_data::ProgramData) -> Result<func_return::FuncReturn, RunkError> {
+    if let rtoken::Rtoken::Plain(string) = &operation.rtoken {
+        let slot = data.funcs.slot(string);
+        return call_function(slot, operation, operands, data);
     }
 
-    match &input[i].rtoken {
-        rtoken::Rtoken::FunctionStart => {
-            let (func_result, func_i) = resolve_exp(&input[i..], &info, data);
-            return Some((func_result, i+func_i));
-        },
-        rtoken::Rtoken::LableLiteral(l) => {
-            return Some((
-                func_return::FuncReturn {
-                    var: Ok(var::Var::n(Zero::zero()).unwrap()),
-                    jump_to: Some(l.clone()),
-                },
-                i+1
-            ))
-        },
-        _ => {
-            fatal_error(&info, format!("Token \"{}\" cannot be used with \"->\"!", &input[i]), Some(&input[i]));
-        }
-    };
+    Ok(func_return::FuncReturn::error(format!(
+        "Invalid token given as function name \"{}\"." , &operation), Some(operation.clone())))
 }
 
 
@@ -76,245 +43,214 @@ fn try_replacement_function(input: &[word::Word],
 /// Deals with runk argument types and conversions and calls runk functions.
 ///
 /// # Arguments
+/// - `slot`: Slot of the function.
 /// - `operation`: Word containing the function name.
 /// - `operands`: Vector of Words supplied as arguments to the function.
 /// - `data`: Internal state of the program.
 ///
 /// # Returns
-/// - `FuncReturn`: Struct with information souch as result of the expression, return value and a request to jump to a lable.
-fn execute_function(operation: &word::Word,
-                    operands: &mut Vec<var::Var>,
-                    data: &program_data::ProgramData) -> func_return::FuncReturn {
-    let string;
-    if let rtoken::Rtoken::Plain(string_) = &operation.rtoken {
-        string = string_.to_string();
-    }
-    else {
-        return func_return::FuncReturn::error(format!(
-            "Invalid token given as function name \"{}\"." , &operation), Some(operation.clone()));
-    }
-    match data.funcs.get(&string) {
-        Some(f) => {
-            match &f.args {
-                // Need to ensure that the supplied operands only contains var variants
-                // specified in 'vec'
-                func::ArgSpec::Unlimited(vec) => {
-                    for i in 0..operands.len() {
-                        let mut found = false;
-                        for supported in vec {
-                            if operands[i].eq_type(supported) {
-                                found = true;
-                            }
-                        }
-                        if !found {
-                            // This var's type was not found in supported type.
-                            // We will now try to convert our unsupported variables
-                            // to any of the supported types.
-                            // We will start from the first supported and move down and
-                            // we will go with the first type that works.
-                            for supported in vec {
-                                let mut temp = supported.clone();
-                                match operands[i].fit_into(&mut temp) {
-                                    Ok(_var) => {
-                                        operands[i] = temp;
-                                        found = true;
-                                        break;
-                                    },
-                                    Err(_e) => continue,
-                                };
-                            }
-                            if !found {
-                                return func_return::FuncReturn::error(format!(
-                                    "Unsupported argument type \"{}\" supplied to function \"{}\". (Supported types: {:?})" ,
-                                    &operands[i], &string, vec), Some(operation.clone()));
-                            }
-                        }
-                    }
-                },
-                // Need to ensure the order and variants of supplied operands matches
-                // those specified in vec
-                func::ArgSpec::Limited(vec) => {
-                    // Ensure length of supplied (operands) arguments matches that of reqired args (vec).
-                    if operands.len() != vec.len() {
-                        return func_return::FuncReturn::error(format!(
-                            "Call to function \"{}\" doesn't have the required number of argumets! Those are: {:?}." ,
-                            &string, vec), Some(operation.clone()));
+/// - `Ok`: Struct with information souch as result of the expression, return value and a request to jump to a lable.
+///   Failure of the function is stored inside of it, so it can be handled with a replacement function.
+/// - `Err`: Error that stops the program, like one inside the body of a function defined in runk.
+fn call_function(slot: usize,
+                 operation: &word::Word,
+                 operands: &mut [var::Var],
+                 data: &mut program_data::ProgramData) -> Result<func_return::FuncReturn, RunkError> {
+    let f = match data.funcs.get_slot(slot) {
+        Some(f) => f,
+        None => return Ok(func_return::FuncReturn {
+            var: Result::Err((format!("Function not found \"{}\"", data.funcs.name(slot)), Some(operation.clone()))),
+            jump_to: None,
+        }),
+    };
+    let string = data.funcs.name(slot);
+
+    match &f.args {
+        // Need to ensure that the supplied operands only contains var variants
+        // specified in 'vec'
+        func::ArgSpec::Unlimited(vec) => {
+            for operand in operands.iter_mut() {
+                let mut found = false;
+                for supported in vec {
+                    if operand.eq_type(supported) {
+                        found = true;
                     }
-                    for i in 0..operands.len() {
-                        // if !operands[i].eq_type(&vec[i]) {
-                        // return func_return::FuncReturn::error(format!(
-                        //     "Function \"{}\" called with incorrect argument type." ,
-                        //     &operation.string));
-                        // }
-                        operands[i] = match operands[i].fit_into(&mut vec[i].clone()) {
-                            Ok(var) => {
-                                var.clone()
+                }
+                if !found {
+                    // This var's type was not found in supported type.
+                    // We will now try to convert our unsupported variables
+                    // to any of the supported types.
+                    // We will start from the first supported and move down and
+                    // we will go with the first type that works.
+                    for supported in vec {
+                        let mut temp = supported.clone();
+                        match operand.fit_into(&mut temp) {
+                            Ok(_var) => {
+                                *operand = temp;
+                                found = true;
+                                break;
                             },
-                            Err(e) => return func_return::FuncReturn::error(e.clone(), Some(operation.clone())),
+                            Err(_e) => continue,
                         };
                     }
+                    if !found {
+                        return Ok(func_return::FuncReturn::error(format!(
+                            "Unsupported argument type \"{}\" supplied to function \"{}\". (Supported types: {:?})" ,
+                            operand, &string, vec), Some(operation.clone())));
+                    }
                 }
-            };
-
-            let mut result = (f.func)(operands);
-            if let Err((s, _opt_w)) = result.var {
-                result.var = Err((s, Some(operation.clone())));
             }
-            return result;
-        },
-        None => func_return::FuncReturn {
-            var: Result::Err((format!("Function not found \"{}\"", &string), Some(operation.clone()))),
-            jump_to: None,
         },
-    }
-
-}
-
-/// # Description
-/// Entry function for resolving runk function calls. It mainly handles collecting information from `input` about the function
-/// and calls `execute_function` to handle the rest.
-///
-/// # Args
-/// - `input`: Word array cropped from the left to the expected begining of the expression
-///            no cropping from the right though.
-/// - `info`: Info about the source that is beeing processed.
-/// - `data`: Internal state of the program.
-///
-/// # Returns
-/// - `FuncReturn`: Struct with information souch as result of the expression, return value and a request to jump to a lable.
-/// - `usize`: Index of last Word in `input` relevant to the expression processing.
-fn resolve_function_expression(input: &[word::Word],
-                               info: &source_info::SourceInfo,
-                               data: &mut program_data::ProgramData) -> (func_return::FuncReturn, usize) {
-    let mut _jump_to: Option<usize> = None;
-    let mut operation: Option<word::Word> = None;
-    let mut operands: Vec<var::Var> = Vec::new();
-    let mut i: usize = 0;
-
-    while i != input.len() {
-        if let rtoken::Rtoken::FunctionEnd = input[i].rtoken {
-            match operation {
-                None => fatal_error(&info, format!("Function name is missing"), Some(&input[i])),
-                Some(op) => {
-                    let mut result = execute_function(&op, &mut operands, &data);
-                    // If the current fucntion fails, we try to call a replacement function that
-                    // follows the original function after an OnFunctionFail token.
-                    if let Result::Err((s, _w)) = &result.var {
-                        data.set_error(s.clone());
-                        let fail = try_replacement_function(&input[i..], &info, data);
-                        // Onfail function successfull
-                        if fail.is_some() {
-                            let (fail_result, fail_i) = fail.unwrap();
-                            result = fail_result;
-                            i += fail_i;
-                        }
-                        return (result, i+1);
-                    }
-                    return (result, i+2);
+        // Need to ensure the order and variants of supplied operands matches
+        // those specified in vec
+        func::ArgSpec::Limited(vec) => {
+            // Ensure length of supplied (operands) arguments matches that of reqired args (vec).
+            if operands.len() != vec.len() {
+                return Ok(func_return::FuncReturn::error(format!(
+                    "Call to function \"{}\" doesn't have the required number of argumets! Those are: {:?}." ,
+                    &string, vec), Some(operation.clone())));
+            }
+            for i in 0..operands.len() {
+                // Values of the right type don't need converting.
+                if operands[i].eq_type(&vec[i]) {
+                    continue;
                 }
+                operands[i] = match operands[i].fit_into(&mut vec[i].clone()) {
+                    Ok(var) => {
+                        var.clone()
+                    },
+                    Err(e) => return Ok(func_return::FuncReturn::error(e.clone(), Some(operation.clone()))),
+                };
             }
         }
+    };
 
-        // First operand in a function is the operation.
-        if i == 0 {
-            operation = Some(input[i].clone());
-            i += 1;
-            continue;
-        }
-
-        let (var, end_index) = resolve_exp(&input[i..], &info, data);
-        if var.var.is_err() {
-            return (var, end_index);
-        }
-
-        operands.push(var.var.unwrap());
-        i += end_index;
+    // Only the body is cloned, functions defined in runk are behind a pointer.
+    let mut result = match f.func.clone() {
+        func::FuncBody::Primitive(op) => op(operands),
+        func::FuncBody::User(user_func) => crate::run_runk_function(&user_func, operands, data)?,
+        func::FuncBody::Native(closure) => (closure.borrow_mut())(operands),
+    };
+    if let Err((s, _opt_w)) = result.var {
+        result.var = Err((s, Some(operation.clone())));
     }
-
-    fatal_error(&info, format!("Expressions ended abruptly!"), Some(&input[0]));
+    Ok(result)
 }
 
 
 /// # Description
-/// Takes Word with `VariableReference` rtoken and returns it's variables value.
+/// Main entry function for expression resolution. Runs the compiled expression of a line on
+/// a stack machine.
 ///
 /// # Args
-/// - `input`: Word which will be resolved as `VariableReference`
+/// - `chunk`: Compiled expression.
 /// - `data`: Internal state of the program.
 ///
 /// # Returns
-/// Value stored in a variable named `input`
-fn resolve_var(input: &word::Word,
-               data: &program_data::ProgramData) -> func_return::FuncReturn {
-    if let rtoken::Rtoken::VariableReference(string) = &input.rtoken {
-        return func_return::FuncReturn {
-            var: match data.vars.get(string) {
-                None => {
-                    Err((format!("Variable \"{}\" was not found.", string.italic()), Some(input.clone())))
-                },
-                Some(num) => {
-                    Ok((*num).clone())
+/// - `Ok`: Struct with information souch as result of the expression, return value and a request to jump to a lable.
+/// - `Err`: Error that stops the program.
+pub fn resolve_exp(chunk: &bytecode::Chunk,
+                   data: &mut program_data::ProgramData) -> Result<func_return::FuncReturn, RunkError> {
+    let mut stack: Vec<var::Var> = Vec::new();
+    // Alternatives of calls that are beeing resolved: index of the alternative and size of the
+    // stack when the call started.
+    let mut handlers: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+
+    while i < chunk.ops.len() {
+        let result = match &chunk.ops[i] {
+            bytecode::Op::Push(value) => {
+                stack.push(value.clone());
+                i += 1;
+                continue;
+            },
+            bytecode::Op::Load(slot, word) => match data.vars.get_slot(*slot) {
+                Some(value) => {
+                    stack.push(value.clone());
+                    i += 1;
+                    continue;
                 },
+                None => func_return::FuncReturn::error(format!("Variable \"{}\" was not found.", data.vars.name(*slot).italic()),
+                                                       Some(chunk.words[*word].clone())),
             },
-            jump_to: None,
+            bytecode::Op::Call(slot, argc, word) => {
+                let mut operands = stack.split_off(stack.len() - argc);
+                call_function(*slot, &chunk.words[*word], &mut operands, data)?
+            },
+            bytecode::Op::Concat(count) => {
+                let text: String = stack.drain(stack.len() - count..).map(|v| v.plain_string()).collect();
+                stack.push(var::Var::T(text));
+                i += 1;
+                continue;
+            },
+            bytecode::Op::List(count) => {
+                let items = stack.split_off(stack.len() - count);
+                stack.push(var::Var::A(items));
+                i += 1;
+                continue;
+            },
+            bytecode::Op::Map(count, word) => {
+                let mut map = std::collections::BTreeMap::new();
+                let mut error = None;
+                let mut items = stack.split_off(stack.len() - 2*count).into_iter();
+                while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                    match key {
+                        var::Var::T(key) => { map.insert(key, value); },
+                        _ => {
+                            error = Some(format!("Key of a map has to be text, not \"{}\"!", key));
+                            break;
+                        },
+                    }
+                }
+                match error {
+                    Some(e) => func_return::FuncReturn::error(e, Some(chunk.words[*word].clone())),
+                    None => {
+                        stack.push(var::Var::M(map));
+                        i += 1;
+                        continue;
+                    },
+                }
+            },
+            bytecode::Op::Try(handler) => {
+                handlers.push((*handler, stack.len()));
+                i += 1;
+                continue;
+            },
+            bytecode::Op::EndTry(end) => {
+                handlers.pop();
+                i = *end;
+                continue;
+            },
+            bytecode::Op::JumpLable(lable) => return Ok(func_return::FuncReturn {
+                var: Ok(var::Var::n(Zero::zero()).unwrap()),
+                jump_to: Some(func_return::Jump::Lable(lable.clone())),
+            }),
         };
-    }
 
-    func_return::FuncReturn::error(format!("Invalid token \"{}\" for a variable.", input.original.italic()), Some(input.clone()))
-}
-
-
-/// # Description
-/// Main entry function for expression resolution. This function is also called recursively by `resolve_function_expression`.
-///
-/// # Args
-/// - `input`: Word array cropped from the left to the expected begining of the expression
-///            no cropping from the right though.
-/// - `info`: Info about the source that is beeing processed.
-/// - `data`: Internal state of the program.
-///
-/// # Returns
-/// - `FuncReturn`: Struct with information souch as result of the expression, return value and a request to jump to a lable.
-/// - `usize`: Index of last Word in `input` relevant to the expression processing.
-pub fn resolve_exp(input: &[word::Word],
-                   info: &source_info::SourceInfo,
-                   data: &mut program_data::ProgramData) ->  (func_return::FuncReturn, usize){
-    if input.len() < 1 {
-        fatal_error(&info, format!("Missing expression!"), None);
-    }
-
-    // Function expression
-    if let rtoken::Rtoken::FunctionStart = input[0].rtoken {
-        if input.len() < 2 {
-            fatal_error(&info, format!("Expression ends abruptly!"), Some(&input[1]));
+        match result.var {
+            // Jump requested by a function happens right away.
+            Ok(_) if result.jump_to.is_some() => return Ok(result),
+            Ok(value) => {
+                stack.push(value);
+                i += 1;
+            },
+            // If the current fucntion fails, we try to call a replacement function that
+            // follows the original function after an OnFunctionFail token.
+            Err((s, opt_w)) => {
+                data.set_error(s.clone());
+                match handlers.pop() {
+                    Some((handler, size)) => {
+                        stack.truncate(size);
+                        i = handler;
+                    },
+                    None => return Ok(func_return::FuncReturn {
+                        var: Err((s, opt_w)),
+                        jump_to: None,
+                    }),
+                }
+            },
         }
-        return resolve_function_expression(&input[1..], &info, data);
-    }
-
-    // Resolve variable
-    if let rtoken::Rtoken::VariableReference(_string) = &input[0].rtoken {
-        return (resolve_var(&input[0], data), 1);
-    }
-
-    // Resolve literal
-    match &input[0].rtoken {
-        rtoken::Rtoken::TextLiteral(_string) => return (func_return::FuncReturn {
-            var: Ok(var::Var::text_from_word(&input[0]).unwrap()),
-            jump_to: None,
-            }, 1
-        ),
-        rtoken::Rtoken::LableLiteral(_string) => return (func_return::FuncReturn {
-            var: Ok(var::Var::lable_from_word(&input[0]).unwrap()),
-            jump_to: None,
-            }, 1
-        ),
-        rtoken::Rtoken::NumLiteral(_string) => return (func_return::FuncReturn {
-            var: Ok(var::Var::num_from_word(&input[0]).unwrap()),
-            jump_to: None,
-            }, 1
-        ),
-        _ => {},
     }
 
-    return (func_return::FuncReturn::error(format!("Invalid token \"{}\" in a function.", input[0].original.italic()), Some(input[0].clone())), 1) ;
+    Ok(func_return::FuncReturn::value(stack.pop().unwrap()))
 }
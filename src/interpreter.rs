@@ -0,0 +1,551 @@
+//!
+//! Interface for running runk code from Rust programs.
+//!
+
+use std::io::{ self, BufRead };
+
+use crate::structs::{ var, word, program_data, source_info };
+use crate::structs::func::{ self, func_return };
+use crate::structs::runk_error::{ RunkError, ErrorKind };
+use crate::parser::rtoken;
+use crate::expressions::execute_function;
+
+/// Name of the file used in errors from code run with `Interpreter::run_str`.
+const STRING_FILE_NAME: &str = "<string>";
+/// Name of the file used in errors from functions called with `Interpreter::call`.
+const CALL_FILE_NAME: &str = "<call>";
+
+/// Runk interpreter meant for embedding runk into other programs. Variables and
+/// functions are kept between runs, so a program can be loaded once and its
+/// functions called repeatedly.
+///
+/// ```
+/// use runk::Interpreter;
+/// use runk::structs::var::Var;
+///
+/// let mut interpreter = Interpreter::new();
+/// interpreter.set_var("x", Var::new(20));
+/// interpreter.run_str("<fn Int add Int a Int b\n    ret [$a + $b]\n>\nInt y: (add $x 1)\n").unwrap();
+///
+/// assert_eq!(interpreter.get_var("y"), Some(&Var::new(21)));
+/// assert_eq!(interpreter.call("add", &[Var::new(1), Var::new(2)]).unwrap(), Var::new(3));
+/// ```
+pub struct Interpreter {
+    data: program_data::ProgramData,
+}
+
+impl Interpreter {
+    /// Creates an interpreter with primitive functions and no variables.
+    pub fn new() -> Interpreter {
+        Interpreter {
+            data: program_data::ProgramData::new(false),
+        }
+    }
+
+    /// Enables printing of every executed line and of the program state at the end of each run.
+    pub fn set_debug(&mut self, debug: bool) {
+        self.data.debug = debug;
+    }
+
+    /// Makes every run read the whole code first, so all lables are known before it starts.
+    /// Duplicate lables, lable literals refering to lables that don't exist and assignments
+    /// to constants are reported before any line is run. Not suitable for code typed in by a user.
+    pub fn set_prescan_lables(&mut self, prescan: bool) {
+        self.data.prescan_lables = prescan;
+    }
+
+    /// Sets how deep calls of functions defined in runk code can be nested. Every call takes
+    /// some stack, so the limit has to fit the stack of the thread that runs the code.
+    /// The default is `program_data::DEFAULT_MAX_CALL_DEPTH`.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.data.max_call_depth = depth;
+    }
+
+    /// Runtime data of the runk program.
+    pub fn data(&self) -> &program_data::ProgramData {
+        &self.data
+    }
+
+    /// Runtime data of the runk program.
+    pub fn data_mut(&mut self) -> &mut program_data::ProgramData {
+        &mut self.data
+    }
+
+    /// # Description
+    /// Makes a closure callable from runk code as a function. Functions defined in runk
+    /// code can't replace it.
+    ///
+    /// ```
+    /// use runk::Interpreter;
+    /// use runk::structs::var::Var;
+    /// use runk::structs::func::{ ArgSpec, func_return::FuncReturn };
+    ///
+    /// let mut interpreter = Interpreter::new();
+    /// let mut calls = 0;
+    /// interpreter.add_function("count", ArgSpec::Limited(vec!()), move |_args| {
+    ///     calls += 1;
+    ///     FuncReturn::value(Var::new(calls))
+    /// });
+    /// interpreter.run_str("(count)\nNat x: (count)\n").unwrap();
+    ///
+    /// assert_eq!(interpreter.get_var("x"), Some(&Var::new(2)));
+    /// ```
+    ///
+    /// # Arguments
+    /// - `name`: Name of the function in runk code.
+    /// - `args`: Arguments the function accepts. They are converted before the closure is called.
+    /// - `closure`: Code of the function.
+    pub fn add_function(&mut self,
+                        name: &str,
+                        args: func::ArgSpec,
+                        closure: impl FnMut(&[var::Var]) -> func_return::FuncReturn + 'static) {
+        self.data.funcs.insert(name.to_string(), func::Func::native(args, closure));
+    }
+
+    /// Returns the value of a variable or `None` if it isn't defined.
+    pub fn get_var(&self, name: &str) -> Option<&var::Var> {
+        self.data.vars.get(name)
+    }
+
+    /// Defines a variable or replaces the value of an existing one. The type of the variable
+    /// is the type of `value`.
+    pub fn set_var(&mut self, name: &str, value: var::Var) {
+        self.data.vars.insert(name.to_string(), value);
+    }
+
+    /// # Description
+    /// Runs runk source code stored in a string.
+    ///
+    /// # Returns
+    /// - `Ok`: The code ran out.
+    /// - `Err`: Error that stopped the code.
+    pub fn run_str(&mut self, source: &str) -> Result<(), RunkError> {
+        self.run_reader(&mut io::Cursor::new(source), STRING_FILE_NAME, false)
+    }
+
+    /// # Description
+    /// Runs runk source code read from `reader` until it ends. If the code is stopped by an
+    /// error, the reader is left right after the line that caused it, so the rest of it
+    /// can be run by calling this method again.
+    ///
+    /// # Arguments
+    /// - `reader`: Source of runk source code.
+    /// - `file_name`: Name of the source shown in errors.
+    /// - `repl_mode`: Whether to show a prompt before reading each line.
+    ///
+    /// # Returns
+    /// - `Ok`: The code ran out.
+    /// - `Err`: Error that stopped the code. A call to "exit" is reported as an error of kind `ErrorKind::Exit`.
+    pub fn run_reader(&mut self, reader: &mut dyn BufRead, file_name: &str, repl_mode: bool) -> Result<(), RunkError> {
+        crate::run_runk_buffer(reader, file_name, &mut self.data, repl_mode)
+    }
+
+    /// # Description
+    /// Calls a runk function, either a primitive one or one defined by code that was already run.
+    ///
+    /// # Arguments
+    /// - `name`: Name of the function.
+    /// - `args`: Arguments of the function. They are converted to the types the function expects.
+    ///
+    /// # Returns
+    /// - `Ok`: Value returned by the function.
+    /// - `Err`: The function failed or tried to jump to a lable.
+    pub fn call(&mut self, name: &str, args: &[var::Var]) -> Result<var::Var, RunkError> {
+        let original = format!("({}{})", name, args.iter().map(|a| format!(" {}", a.plain_string())).collect::<String>());
+        let info = source_info::SourceInfo::new(1, CALL_FILE_NAME, original);
+        let operation = word::Word {
+            rtoken: rtoken::Rtoken::Plain(name.to_string()),
+            original: name.to_string(),
+            column: 1,
+            line: 1,
+            parsed_line: 0,
+        };
+
+        let result = self.data.with_decimal_context(|data| execute_function(&operation, &mut args.to_vec(), data));
+        // Error inside of a function leaves its scopes open.
+        self.data.vars.reset();
+        let result = result?;
+        let value = match result.var {
+            Ok(v) => v,
+            Err((s, opt_w)) => {
+                self.data.set_error(s.clone());
+                return Err(RunkError::new(ErrorKind::Runtime, s, &info, opt_w.as_ref()));
+            },
+        };
+
+        match result.jump_to {
+            None => Ok(value),
+            Some(func_return::Jump::Exit(code)) => Err(RunkError::exit(code)),
+            Some(func_return::Jump::Lable(_)) => Err(RunkError::new(
+                ErrorKind::Runtime, format!("Function \"{}\" can't jump outside of a program!", name), &info, Some(&operation))),
+        }
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[test]
+fn test_run_after_error() {
+    let mut interpreter = Interpreter::new();
+    let mut reader = io::Cursor::new("Nat a: 1\nNat b: (nope)\nNat c: 3\n");
+
+    let error = interpreter.run_reader(&mut reader, "test", false).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Runtime);
+    assert_eq!(error.line_number, 2);
+    assert_eq!(error.column, Some(8));
+    assert!(interpreter.get_var("b").is_none());
+
+    // Rest of the code can still be run.
+    interpreter.run_reader(&mut reader, "test", false).unwrap();
+    assert_eq!(interpreter.get_var("a"), Some(&var::Var::new(1)));
+    assert_eq!(interpreter.get_var("c"), Some(&var::Var::new(3)));
+}
+
+#[test]
+fn test_call() {
+    let mut interpreter = Interpreter::new();
+    assert_eq!(interpreter.call("+", &[var::Var::new(1), var::Var::new(2)]).unwrap(), var::Var::new(3));
+    assert_eq!(interpreter.call("nope", &[]).unwrap_err().kind, ErrorKind::Runtime);
+    assert_eq!(interpreter.call("exit", &[var::Var::new(2)]).unwrap_err().kind, ErrorKind::Exit(2));
+}
+
+#[test]
+fn test_add_function() {
+    let mut interpreter = Interpreter::new();
+    let prices = std::collections::HashMap::from([("apple".to_string(), 12), ("pear".to_string(), 30)]);
+    interpreter.add_function("price", func::ArgSpec::Limited(vec!(var::Var::T(String::new()))), move |args| {
+        match prices.get(&args[0].plain_string()) {
+            Some(price) => func_return::FuncReturn::value(var::Var::new(*price)),
+            None => func_return::FuncReturn::func_error("Unknown sku!".to_string()),
+        }
+    });
+
+    interpreter.run_str("Nat a: [(price \"apple\") * 2]\nNat b: (price \"plum\") else (price \"pear\")\n").unwrap();
+    assert_eq!(interpreter.get_var("a"), Some(&var::Var::new(24)));
+    assert_eq!(interpreter.get_var("b"), Some(&var::Var::new(30)));
+
+    let error = interpreter.run_str("<fn Nat price Txt sku\n    ret 1\n>\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Runtime);
+}
+
+#[test]
+fn test_max_call_depth() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_max_call_depth(20);
+    interpreter.run_str("<fn Nat down Nat n\n    <if [$n = 0]\n        ret 0\n    >\n    ret [(down [$n - 1]) + 1]\n>\n").unwrap();
+
+    let error = interpreter.run_str("Nat a: (down 30)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Runtime);
+    assert!(error.message.contains("deeper than 20"));
+    assert!(interpreter.call("down", &[var::Var::new(30)]).is_err());
+
+    // Depth of the failed calls doesn't count against later ones.
+    assert_eq!(interpreter.call("down", &[var::Var::new(19)]).unwrap(), var::Var::new(19));
+    interpreter.run_str("Nat b: (down 19)\n").unwrap();
+    assert_eq!(interpreter.get_var("b"), Some(&var::Var::new(19)));
+}
+
+#[test]
+fn test_prescan_lables() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_prescan_lables(true);
+
+    // Nothing is run when a lable is missing.
+    let error = interpreter.run_str("Nat a: 1\n(go !nowhere)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Syntax);
+    assert_eq!(error.line_number, 2);
+    assert!(interpreter.get_var("a").is_none());
+
+    let error = interpreter.run_str("!x\nNat a: 1\n!x\n").unwrap_err();
+    assert_eq!(error.line_number, 3);
+    assert!(interpreter.get_var("a").is_none());
+
+    interpreter.run_str("Nat a: 1\n(go !end)\na: 2\n!end\n").unwrap();
+    assert_eq!(interpreter.get_var("a"), Some(&var::Var::new(1)));
+}
+
+#[test]
+fn test_validate_before_run() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_prescan_lables(true);
+
+    // Invalid line at the end stops the code before it starts.
+    let error = interpreter.run_str("Nat a: 1\n<if 1\n    (print \"x\") 2\n>\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Parse);
+    assert_eq!(error.line_number, 3);
+    assert!(interpreter.get_var("a").is_none());
+
+    interpreter.run_str("Nat a: (+ $missing 1) else (+ 1 1)\n").unwrap();
+    assert_eq!(interpreter.get_var("a"), Some(&var::Var::new(2)));
+}
+
+#[test]
+fn test_slots() {
+    let mut interpreter = Interpreter::new();
+    // Function is called through its slot, so defining it later is fine.
+    interpreter.run_str("<fn Nat twice Nat n\n    ret (* $n 2)\n>\n").unwrap();
+    interpreter.run_str("Nat a: (twice 2)\n<fn Nat thrice Nat n\n    ret (twice (+ $n $n $n))\n>\nNat b: (thrice 1)\n").unwrap();
+    assert_eq!(interpreter.get_var("a"), Some(&var::Var::new(4)));
+    assert_eq!(interpreter.get_var("b"), Some(&var::Var::new(6)));
+
+    // Variables of a function don't leak out of it.
+    interpreter.run_str("<fn Nat f Nat n\n    Nat inner: $n\n    ret $inner\n>\nNat c: (f 5)\n").unwrap();
+    assert_eq!(interpreter.get_var("c"), Some(&var::Var::new(5)));
+    assert!(interpreter.get_var("inner").is_none());
+
+    interpreter.run_str("Nat d: (nat (/ 1 0) else (nope)) else (+ 1 2)\n").unwrap();
+    assert_eq!(interpreter.get_var("d"), Some(&var::Var::new(3)));
+}
+
+#[test]
+fn test_scopes() {
+    let mut interpreter = Interpreter::new();
+
+    // Declaration inside a block shadows the outer variable, even with a different type.
+    interpreter.run_str("Nat n: 1\n<if 1\n    Txt n: \"inner\"\n    Nat m: 2\n    n: \"changed\"\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("n"), Some(&var::Var::new(1)));
+    assert!(interpreter.get_var("m").is_none());
+
+    // Assignment without a type changes the variable from the outer scope.
+    interpreter.run_str("Nat i: 0\n<while [$i < 3]\n    Nat step: 1\n    i: [$i + $step]\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("i"), Some(&var::Var::new(3)));
+    assert!(interpreter.get_var("step").is_none());
+
+    // Same name in unrelated blocks isn't a redefinition.
+    interpreter.run_str("<if 1\n    Nat x: 1\n>\n<if 1\n    Txt x: \"a\"\n>\nTxt x: \"b\"\n").unwrap();
+    let error = interpreter.run_str("<if 1\n    Nat y: 1\n    Txt y: \"a\"\n>\n").unwrap_err();
+    assert_eq!(error.line_number, 3);
+
+    // Functions see global variables but not the ones of their caller.
+    interpreter.run_str("Nat total: 0\n<fn Nat add Nat v\n    total: [$total + $v]\n    ret $total\n>\n(add 4)\n").unwrap();
+    assert_eq!(interpreter.get_var("total"), Some(&var::Var::new(4)));
+    interpreter.run_str("<fn Nat peek Nat v\n    ret $local\n>\n").unwrap();
+    assert!(interpreter.run_str("<if 1\n    Nat local: 1\n    (peek 1)\n>\n").is_err());
+
+    // Jumping out of blocks closes their scopes.
+    interpreter.run_str("<if 1\n    <while 1\n        Nat deep: 1\n        (go !out)\n    >\n>\n!out\nNat deep: 2\n").unwrap();
+    assert_eq!(interpreter.get_var("deep"), Some(&var::Var::new(2)));
+    assert_eq!(interpreter.data().vars.depth(), 0);
+}
+
+#[test]
+fn test_constants() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("const Nat max: 10\n<if 1\n    Int max: 3\n    max: 4\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("max"), Some(&var::Var::new(10)));
+
+    let error = interpreter.run_str("Nat a: 1\nmax: 11\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Runtime);
+    assert_eq!(error.line_number, 2);
+    assert!(error.message.contains("<string>:1"));
+    assert_eq!(interpreter.get_var("max"), Some(&var::Var::new(10)));
+
+    // Assignment is found before anything runs.
+    interpreter.set_prescan_lables(true);
+    let error = interpreter.run_str("Nat b: 1\nconst Nat min: 1\n<fn Nat f Nat x\n    min: $x\n    ret 1\n>\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Syntax);
+    assert_eq!(error.line_number, 4);
+    assert!(error.message.contains("declared at <string>:2"));
+    assert!(interpreter.get_var("b").is_none());
+
+    // Constants of a function are checked when it's defined.
+    interpreter.set_prescan_lables(false);
+    let error = interpreter.run_str("\n<fn Nat g\n    const Nat c: 1\n    c: 2\n    ret $c\n>\n").unwrap_err();
+    assert_eq!(error.line_number, 4);
+    assert!(error.message.contains("declared at <string>:3"));
+}
+
+#[test]
+fn test_lists() {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_var("x", var::Var::new(3));
+    interpreter.run_str("Lst l: {1 $x (+ 2 3)}\nl: (push (set $l 0 \"a\") {})\nNat n: (len $l)\nLst s: (slice (pop $l) 1)\n").unwrap();
+    assert_eq!(interpreter.get_var("n"), Some(&var::Var::new(4)));
+    assert_eq!(interpreter.get_var("s"), Some(&var::Var::a(vec!(var::Var::new(3), var::Var::new(5))).unwrap()));
+    assert_eq!(interpreter.call("at", &[interpreter.get_var("l").unwrap().clone(), var::Var::new(0)]).unwrap(),
+               var::Var::t("a".to_string()).unwrap());
+
+    let error = interpreter.run_str("Nat y: (at $l 4)\n").unwrap_err();
+    assert!(error.message.contains("out of range"));
+    let error = interpreter.run_str("Lst y: (slice {1 2 3} 0 99)\n").unwrap_err();
+    assert!(error.message.contains("out of range of a list with 3 items"));
+    assert!(interpreter.run_str("Nat z: [{1 2} = {1 2}]\nNat w: [$s = {1}]\n").is_ok());
+    assert_eq!(interpreter.get_var("z"), Some(&var::Var::new(1)));
+    assert_eq!(interpreter.get_var("w"), Some(&var::Var::new(0)));
+}
+
+#[test]
+fn test_maps() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Lst words: {\"b\" \"a\" \"b\"}\nMap counts: {:}\nNat i: 0\n<while [$i < (len $words)]\n\
+                         Txt w: (at $words $i)\n<if (has $counts $w)\ncounts: (set $counts $w [(get $counts $w) + 1])\n><else\n\
+                         counts: (set $counts $w 1)\n>\ni: [$i + 1]\n>\n").unwrap();
+    let counts = interpreter.get_var("counts").unwrap().clone();
+    assert_eq!(counts.plain_string(), "{\"a\": 1 \"b\": 2}");
+    assert_eq!(interpreter.call("keys", std::slice::from_ref(&counts)).unwrap().plain_string(), "{\"a\" \"b\"}");
+    assert_eq!(interpreter.call("len", std::slice::from_ref(&counts)).unwrap(), var::Var::new(2));
+    assert_eq!(interpreter.call("remove", &[counts, var::Var::t("b".to_string()).unwrap()]).unwrap().plain_string(), "{\"a\": 1}");
+
+    assert!(interpreter.run_str("Nat e: [{\"x\": 1 \"y\": 2} = {\"y\": 2 \"x\": 1}]\n").is_ok());
+    assert_eq!(interpreter.get_var("e"), Some(&var::Var::new(1)));
+    let error = interpreter.run_str("Nat g: (get $counts \"c\")\n").unwrap_err();
+    assert!(error.message.contains("not in the map"));
+    let error = interpreter.run_str("Map m: {1: 2}\n").unwrap_err();
+    assert!(error.message.contains("has to be text"));
+}
+
+#[test]
+fn test_rationals() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Rat r: 3/4\nRat s: [$r + 0.25]\nRat t: [(/ 2 3/1) * -3]\nRat x: 1\nx: (/ $x 3)\n\
+                         Nat n: [2/4 * 4]\nNat c: [$x < 1/2]\nNat e: [2/6 = $x]\n").unwrap();
+    assert_eq!(interpreter.get_var("r").unwrap().plain_string(), "3/4");
+    assert_eq!(interpreter.get_var("s"), Some(&var::Var::new(1)));
+    assert_eq!(interpreter.get_var("t"), Some(&var::Var::new(-2)));
+    assert_eq!(interpreter.get_var("x").unwrap().plain_string(), "1/3");
+    assert_eq!(interpreter.get_var("n"), Some(&var::Var::new(2)));
+    assert_eq!(interpreter.get_var("c"), Some(&var::Var::new(1)));
+    assert_eq!(interpreter.get_var("e"), Some(&var::Var::new(1)));
+    assert_eq!(interpreter.call("rat", &[var::Var::t("-0.125".to_string()).unwrap()]).unwrap().plain_string(), "-1/8");
+    // Text is read the same way as numbers in the code.
+    interpreter.run_str("Int h: (int \"-0xFF\")\nNat k: (nat \"1_000\")\nNat w: (nat \" 4/2 \")\n").unwrap();
+    assert_eq!(interpreter.get_var("h"), Some(&var::Var::new(-255)));
+    assert_eq!(interpreter.get_var("k"), Some(&var::Var::new(1000)));
+    assert_eq!(interpreter.get_var("w"), Some(&var::Var::new(2)));
+    assert!(interpreter.run_str("Nat e: (nat \"-1\")\n").unwrap_err().message.contains("natural number"));
+    assert!(interpreter.run_str("Int e: (int \"1.5\")\n").unwrap_err().message.contains("integer"));
+    assert!(interpreter.run_str("Int e: (int \"1__0\")\n").unwrap_err().message.contains("not a number"));
+
+    let error = interpreter.run_str("Nat f: 1/2\n").unwrap_err();
+    assert!(error.message.contains("fraction"));
+    let error = interpreter.run_str("Rat z: (/ 1/2 0)\n").unwrap_err();
+    assert!(error.message.contains("Division by zero"));
+}
+
+#[test]
+fn test_decimals() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Dec total: [12.50 * 3]\nDec third: [10.00 / 3]\n(scale 2 \"half-up\")\nDec eighth: [1.00 / 8]\n\
+                         Dec r: (round 2/3 3 \"floor\")\nNat n: 2.00\nNat e: [0.50 = 1/2]\n").unwrap();
+    assert_eq!(interpreter.get_var("total").unwrap().plain_string(), "37.50");
+    assert_eq!(interpreter.get_var("third").unwrap().plain_string(), "3.3333333333");
+    assert_eq!(interpreter.get_var("eighth").unwrap().plain_string(), "0.13");
+    assert_eq!(interpreter.get_var("r").unwrap().plain_string(), "0.666");
+    assert_eq!(interpreter.get_var("n"), Some(&var::Var::new(2)));
+    assert_eq!(interpreter.get_var("e"), Some(&var::Var::new(1)));
+
+    let error = interpreter.run_str("Dec x: (round 1.5 2 \"up\")\n").unwrap_err();
+    assert!(error.message.contains("Unknown rounding"));
+    let error = interpreter.run_str("Dec x: (round 1.5 100000)\n").unwrap_err();
+    assert!(error.message.contains("too large"));
+    let error = interpreter.run_str("(scale 4294967295)\n").unwrap_err();
+    assert!(error.message.contains("too large"));
+    // Fractions with an exact decimal form keep it.
+    interpreter.run_str("Dec q: (dec \"3/4\")\nDec t: (dec \"1/3\")\nDec h: 1/8\n").unwrap();
+    assert_eq!(interpreter.get_var("q").unwrap().plain_string(), "0.75");
+    assert_eq!(interpreter.get_var("t").unwrap().plain_string(), "0.33");
+    assert_eq!(interpreter.get_var("h").unwrap().plain_string(), "0.125");
+    // Every program has a scale of its own and starts with the default one.
+    let mut other = Interpreter::new();
+    other.run_str("Dec y: [1.0 / 4]\n").unwrap();
+    assert_eq!(other.get_var("y").unwrap().plain_string(), "0.2500000000");
+    interpreter.run_str("Dec y: [1.0 / 4]\n").unwrap();
+    assert_eq!(interpreter.get_var("y").unwrap().plain_string(), "0.25");
+    assert_eq!(interpreter.call("/", &[var::Var::dec_from_str("2.0").unwrap(), var::Var::new(3)]).unwrap().plain_string(), "0.67");
+    assert_eq!(other.call("/", &[var::Var::dec_from_str("2.0").unwrap(), var::Var::new(3)]).unwrap().plain_string(), "0.6666666667");
+}
+
+#[test]
+fn test_number_primitives() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Nat r: [17 % 5 * 2]\nInt n: (% -7 3)\nNat p: (pow 4 13 497)\nNat g: (gcd 12 18 -30)\nNat l: (lcm 4 6 10)\n\
+                         Nat s: (isqrt 99)\nInt m: (min 3 -2 5)\nDec x: (max 1/2 0.75)\nDec a: (abs -12.50)\nInt z: (sign -4)\n").unwrap();
+    assert_eq!(interpreter.get_var("r"), Some(&var::Var::new(4)));
+    assert_eq!(interpreter.get_var("n"), Some(&var::Var::new(-1)));
+    assert_eq!(interpreter.get_var("p"), Some(&var::Var::new(445)));
+    assert_eq!(interpreter.get_var("g"), Some(&var::Var::new(6)));
+    assert_eq!(interpreter.get_var("l"), Some(&var::Var::new(60)));
+    assert_eq!(interpreter.get_var("s"), Some(&var::Var::new(9)));
+    assert_eq!(interpreter.get_var("m"), Some(&var::Var::new(-2)));
+    assert_eq!(interpreter.get_var("x").unwrap().plain_string(), "0.75");
+    assert_eq!(interpreter.get_var("a").unwrap().plain_string(), "12.50");
+    assert_eq!(interpreter.get_var("z"), Some(&var::Var::new(-1)));
+    assert_eq!(interpreter.call("pow", &[var::Var::new(2), var::Var::new(100)]).unwrap().plain_string(),
+               "1267650600228229401496703205376");
+
+    assert!(interpreter.run_str("Nat e: (pow 2 -1)\n").unwrap_err().message.contains("can't be negative"));
+    assert!(interpreter.run_str("Nat e: (pow 3 4000000000)\n").unwrap_err().message.contains("too large"));
+    assert!(interpreter.run_str("Nat e: (pow 3 99999999999999999999)\n").unwrap_err().message.contains("too large"));
+    assert_eq!(interpreter.call("pow", &[var::Var::new(-1), var::Var::new(4000000001_i64)]).unwrap(), var::Var::new(-1));
+    assert!(interpreter.run_str("Nat e: (isqrt -4)\n").unwrap_err().message.contains("negative"));
+    assert!(interpreter.run_str("Nat e: (% 1 0)\n").unwrap_err().message.contains("Division by zero"));
+}
+
+#[test]
+fn test_bitwise_primitives() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Nat a: (band 12 10 -1)\nInt o: (bor -8 3)\nInt x: (bxor -1 5)\nInt n: (bnot 5)\nNat l: (shl 3 4)\n\
+                         Int r: (shr -7 1)\nNat c: (popcount 255)\nNat b: (bit -2 1000)\nNat z: (bit 5 1)\n").unwrap();
+    assert_eq!(interpreter.get_var("a"), Some(&var::Var::new(8)));
+    assert_eq!(interpreter.get_var("o"), Some(&var::Var::new(-5)));
+    assert_eq!(interpreter.get_var("x"), Some(&var::Var::new(-6)));
+    assert_eq!(interpreter.get_var("n"), Some(&var::Var::new(-6)));
+    assert_eq!(interpreter.get_var("l"), Some(&var::Var::new(48)));
+    assert_eq!(interpreter.get_var("r"), Some(&var::Var::new(-4)));
+    assert_eq!(interpreter.get_var("c"), Some(&var::Var::new(8)));
+    assert_eq!(interpreter.get_var("b"), Some(&var::Var::new(1)));
+    assert_eq!(interpreter.get_var("z"), Some(&var::Var::new(0)));
+
+    assert!(interpreter.run_str("Nat e: (popcount -1)\n").unwrap_err().message.contains("negative"));
+    assert!(interpreter.run_str("Nat e: (shl 1 18446744073709551615)\n").unwrap_err().message.contains("too large"));
+    assert!(interpreter.run_str("Nat e: (shl 1 4000000000)\n").unwrap_err().message.contains("too large"));
+}
+
+#[test]
+fn test_radix_and_format() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Nat h: (frombase \"fF\" 16)\nInt z: (frombase \"-z\" 36)\nTxt b: (tobase -10 2)\nTxt x: (tobase $h 16)\n\
+                         Txt a: (fmt 1234567 \",\")\nTxt p: (fmt 42 \"+05\")\nTxt w: (fmt -1234.50 \"10_\")\n").unwrap();
+    assert_eq!(interpreter.get_var("h"), Some(&var::Var::new(255)));
+    assert_eq!(interpreter.get_var("z"), Some(&var::Var::new(-35)));
+    let text = |name: &str| interpreter.get_var(name).unwrap().plain_string();
+    assert_eq!(text("b"), "-1010");
+    assert_eq!(text("x"), "ff");
+    assert_eq!(text("a"), "1,234,567");
+    assert_eq!(text("p"), "+0042");
+    assert_eq!(text("w"), " -1_234.50");
+
+    assert!(interpreter.run_str("Nat e: (frombase \"19\" 8)\n").unwrap_err().message.contains("base 8"));
+    assert!(interpreter.run_str("Txt e: (tobase 1 37)\n").unwrap_err().message.contains("from 2 to 36"));
+    assert!(interpreter.run_str("Txt e: (fmt 1 \"x\")\n").unwrap_err().message.contains("Invalid format"));
+    assert!(interpreter.run_str("Txt e: (fmt 1 \"70000\")\n").unwrap_err().message.contains("larger than 65535"));
+}
+
+#[test]
+fn test_text_primitives() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Txt t: (trim \"  žluťoučký kůň \")\nNat l: (len $t)\nTxt s: (substr $t 10 13)\nNat f: (find $t \"kůň\")\n\
+                         Txt u: (upper $t)\nTxt r: (replace \"a-b-c\" \"-\" \"+\")\nLst p: (split \"a,,b\" \",\")\n\
+                         Nat b: [(starts $t \"žlu\") and (not (ends $t \"x\"))]\nTxt x: (repeat (lower \"Ab\") 2)\n").unwrap();
+    let text = |name: &str| interpreter.get_var(name).unwrap().plain_string();
+    assert_eq!(text("t"), "žluťoučký kůň");
+    assert_eq!(text("l"), "13");
+    assert_eq!(text("s"), "kůň");
+    assert_eq!(text("f"), "10");
+    assert_eq!(text("u"), "ŽLUŤOUČKÝ KŮŇ");
+    assert_eq!(text("r"), "a+b+c");
+    assert_eq!(text("p"), "{\"a\" \"\" \"b\"}");
+    assert_eq!(text("b"), "1");
+    assert_eq!(text("x"), "abab");
+
+    let error = interpreter.run_str("Txt e: (substr $t 2 14)\n").unwrap_err();
+    assert!(error.message.contains("out of range of a text with 13 characters"));
+    assert!(interpreter.run_str("Nat e: (find $t \"?\")\n").is_err());
+    let error = interpreter.run_str("Txt e: (repeat \"ab\" 18446744073709551615)\n").unwrap_err();
+    assert!(error.message.contains("too long"));
+}
@@ -1,71 +1,25 @@
 use std::collections;
+use std::borrow::Cow;
 use colored::Colorize;
+use num_traits::Zero;
 use std::io::{ BufRead, Write };
 
 use crate::structs::{var, assign, program_data, word, source_info, line };
-use crate::prints::fatal_error;
+use crate::structs::func::{ self, func_return, user_func };
+use crate::structs::runk_error::{ RunkError, ErrorKind };
 use crate::expressions::resolve_exp;
-use crate::parser::{ rtoken, ParseResult };
+use crate::parser::{ ast, rtoken, ParseResult };
 
 pub mod parser;
 pub mod structs;
 pub mod expressions;
+pub mod bytecode;
 #[macro_use]
 pub mod prints;
+pub mod interpreter;
+pub mod capi;
 
-/// # Description
-/// Creates an assignment struct from a Word array.
-///
-/// # Arguments
-/// - `input`: array slice of words that may or may not contain an assignment.
-///
-/// # Returns
-/// - `Ok`: An assignment object that contains:
-///     - `Option<Assign>`: Is None if the `input` didn't contain an assignment.
-///     - `usize`: Index of the `Assign` Rtoken in the `input` array.
-/// - `Err`:
-///     - String: Description of the issue intended to be shown to the user.
-///     - Word: The problematic word.
-///
-/// TODO Consider making this an Assign method.
-fn preprocess_assignment<'a>(input: &'a [word::Word]) -> Result<(Option<assign::Assign>, usize), (String, &'a word::Word)> {
-    // Parsing assignment
-    let mut exp_start_index: usize = 0;
-
-    // Look for the asssignment operator ":"
-    for (i, word) in input.iter().enumerate() {
-        if word.rtoken == rtoken::Rtoken::Assign {
-            exp_start_index = i;
-            break;
-        }
-    }
-
-    // Check if expression is missing.
-    if input.len() <= exp_start_index {
-        return Err((format!("Missing expression!"), &input[input.len()-1]));
-    }
-
-    // Nondeclarative
-    if exp_start_index == 1 {
-        if let rtoken::Rtoken::Plain(name) = &input[0].rtoken {
-            return Ok((Some(assign::Assign::Nondec(name.to_string())), exp_start_index+1));
-        }
-        return Err((format!("Variable name \"{}\" is invalid!", input[0].original.italic()), &input[0]));
-    }
-
-    // Declarative
-    if exp_start_index == 2 {
-        if let rtoken::Rtoken::DataType(dtype) = &input[0].rtoken{
-            if let rtoken::Rtoken::Plain(name) = &input[1].rtoken {
-                return Ok((Some(assign::Assign::Dec(dtype.clone(), name.to_string())), exp_start_index+1));
-            }
-            return Err((format!("Variable name \"{}\" is invalid!", input[1].original.italic()), &input[1]));
-        }
-        return Err((format!("Variable type \"{}\" is invalid!", input[0].original.italic()), &input[0]));
-    }
-
-    return Ok((None, exp_start_index));
-}
+pub use interpreter::Interpreter;
 
 /// # Description
 /// Assigns the value to the target specified in `assign`. If `assign` is `None` the value will be printed
@@ -73,14 +27,19 @@ fn preprocess_assignment<'a>(input: &'a [word::Word]) -> Result<(Option<assign::
 ///
 /// # Arguments
 /// - `assign`: Struct containing details of the assignment.
+/// - `slot`: Slot of the assigned variable.
 /// - `value`: A variable with the value that will be assigned.
 /// - `info`: Source information this operation relates to. TODO Remove this.
 /// - `data`: Runtime data of the runk program.
+///
+/// # Returns
+/// - `Err`: The value couldn't be assigned.
 fn execute_assignment(assign: &Option<assign::Assign>,
+                     slot: Option<usize>,
                      value: &var::Var,
                      info: &source_info::SourceInfo,
                      data: &mut program_data::ProgramData,
-                     force_new_line: bool) {
+                     force_new_line: bool) -> Result<(), RunkError> {
     // Processing assignment
     if assign.is_none() {
         print!("{}", value.plain_string());
@@ -92,49 +51,69 @@ fn execute_assignment(assign: &Option<assign::Assign>,
             }
         }
         // Days since caching stdout caused 0issues:
-        return;
+        return Ok(());
     }
 
-    let bruh = assign.as_ref().unwrap().clone();
-    match bruh {
-        assign::Assign::Dec(mut num, ref string) => {
-            if data.vars.contains_key(&string[..]) {
-                // Runk allows "redefinition" of a variable as long as the type is the same.
-                let old = data.vars.get(&string[..]).unwrap();
-                if !num.eq_type(&old) {
-                    fatal_error(&info, format!("Redefinition of variable \"{}\"!", string.italic()), None);
+    let slot = slot.unwrap();
+    match assign.as_ref().unwrap() {
+        assign::Assign::Dec(dtype, string) | assign::Assign::Const(dtype, string) => {
+            let constant = matches!(assign, Some(assign::Assign::Const(_, _)));
+            // Declaration shadows variables from outer scopes.
+            if let Some(old) = data.vars.get_declared(slot) {
+                if let Some(location) = data.vars.constant(slot) {
+                    return Err(RunkError::new(ErrorKind::Runtime, format!("Redefinition of constant \"{}\" declared at {}!", string.italic(), location),
+                                              info, None));
+                }
+                // Runk allows "redefinition" of a variable in the same scope as long as the type is the same.
+                // A variable can't become a constant though.
+                if !dtype.eq_type(old) || constant {
+                    return Err(RunkError::new(ErrorKind::Runtime, format!("Redefinition of variable \"{}\"!", string.italic()), info, None));
                 }
             }
 
             // Copying the acutal number to "num"
+            let mut num = dtype.clone();
             if let Result::Err(e) = value.fit_into(&mut num) {
-                fatal_error(&info, e, None);
+                return Err(RunkError::new(ErrorKind::Runtime, e, info, None));
             }
 
             // New variable is beeing defined. We match agains the type specified
             // in 'line'
-            data.vars.insert((&string[..]).to_string(), num);
+            match constant {
+                true => data.vars.declare_constant(slot, num, format!("{}:{}", info.file_name, info.line_number)),
+                false => data.vars.declare(slot, num),
+            }
         },
         assign::Assign::Nondec(string) => {
-            if !data.vars.contains_key(&string[..]) {
-                fatal_error(&info, format!("Variable \"{}\" assigned before definition!", string.italic()), None);
+            if let Some(location) = data.vars.constant(slot) {
+                return Err(RunkError::new(ErrorKind::Runtime, format!("Cannot assign to constant \"{}\" declared at {}!", string.italic(), location),
+                                          info, None));
             }
+            // Assigns to the innermost declaration that is visible.
+            let old = match data.vars.get_slot_mut(slot) {
+                Some(old) => old,
+                None => return Err(RunkError::new(ErrorKind::Runtime, format!("Variable \"{}\" assigned before definition!", string.italic()), info, None)),
+            };
+            // Only the type of the old value is needed.
+            let mut num = match value.eq_type(old) {
+                true => value.clone(),
+                false => old.clone(),
+            };
 
-            let mut old_num: var::Var = data.vars.get(&string[..]).unwrap().clone();
-
-            if value.fit_into(&mut old_num).is_err() {
-                fatal_error(&info, format!("Failiure while converting numbers during assignment."), None);
+            if value.fit_into(&mut num).is_err() {
+                return Err(RunkError::new(ErrorKind::Runtime, "Failiure while converting numbers during assignment.".to_string(), info, None));
             }
 
-            // New variable is beeing defined. We match agains the type specified
-            // in 'line'
-            data.vars.insert((&string[..]).to_string(), old_num);
+            *old = num;
         },
     }
+
+    Ok(())
 }
 
 /// # Description
-/// Goes through lines looking for lable declarations and adds them to `hash_map`
+/// Goes through lines looking for lable declarations and adds them to `hash_map`. Bodies
+/// of functions are skipped, they have lables of their own.
 ///
 /// # Arguments
 /// - `lines`: List of runk lines to to search.
@@ -144,184 +123,774 @@ fn execute_assignment(assign: &Option<assign::Assign>,
 /// - `Ok`: Amount of labels found.
 /// - `Err`:
 ///     - String: Description of the issue intended to be shown to the user.
+///     - usize: Index of the line with the problematic word.
 ///     - Word: The problematic word.
 fn load_lables<'a>(lines: &'a [line::Line],
-               hash_map: &mut collections::HashMap<String, usize>) -> Result<usize, (String, &'a word::Word)> {
+               hash_map: &mut collections::HashMap<String, usize>) -> Result<usize, (String, usize, &'a word::Word)> {
     let mut counter = 0;
-    for (i, line) in lines.iter().enumerate() {
-        if line.content.len() != 1 {
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        if line.statement.starts_block("fn") {
+            i = find_function_end(lines, i) + 1;
             continue;
         }
 
-        if let rtoken::Rtoken::LableLiteral(lable) = &line.content[0].rtoken {
+        if let ast::Statement::Lable(lable) = &line.statement {
             if hash_map.get(&lable[..]).is_some() {
-                return Result::Err((format!("Redefinition of lable \"{}\".", &lable), &line.content[0]));
+                return Result::Err((format!("Redefinition of lable \"{}\".", &lable), i, &line.content[0]));
             }
 
             hash_map.insert(lable.clone(), i);
             counter += 1;
         }
+        i += 1;
     }
 
     Result::Ok(counter)
 }
 
+/// Returns the index of the ">" closing a function declared at `start` or the index of
+/// the last line if the function isn't closed.
+fn find_function_end(lines: &[line::Line], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        match line.statement {
+            ast::Statement::BlockStart(_, _) => depth += 1,
+            ast::Statement::BlockEnd if depth == 1 => return i,
+            ast::Statement::BlockEnd => depth -= 1,
+            _ => {},
+        }
+    }
+
+    lines.len() - 1
+}
+
+/// # Description
+/// Registers all lables declared in `lines` before they are run and makes sure every lable
+/// literal refers to one of them. Bodies of functions are checked against their own lables.
+///
+/// # Arguments
+/// - `lines`: All lines of the code.
+///
+/// # Returns
+/// - `Ok`: Lables declared in `lines` outside of functions.
+/// - `Err`:
+///     - String: Description of the issue intended to be shown to the user.
+///     - usize: Index of the line with the problematic word.
+///     - Word: The problematic word.
+fn prescan_lables(lines: &[line::Line]) -> Result<collections::HashMap<String, usize>, (String, usize, &word::Word)> {
+    let mut lables = collections::HashMap::new();
+    load_lables(lines, &mut lables)?;
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        if line.statement.starts_block("fn") {
+            let end = find_function_end(lines, i);
+            if let Err((s, body_i, w)) = prescan_lables(&lines[i+1..end]) {
+                return Err((s, i + 1 + body_i, w));
+            }
+            i = end + 1;
+            continue;
+        }
+
+        if let Some(expression) = line.statement.expression() {
+            let mut uses = Vec::new();
+            expression.for_each_lable(&mut |lable, word| uses.push((lable, word)));
+            for (lable, word) in uses {
+                if !lables.contains_key(lable) {
+                    return Err((format!("Lable \"{}\" is not defined.", lable.italic()), i, word));
+                }
+            }
+        }
+        i += 1;
+    }
+
+    Ok(lables)
+}
+
+/// # Description
+/// Makes sure no line of `lines` assigns to a constant before they are run. Follows blocks
+/// and functions the same way scopes are opened when the code runs, jumps are not taken into account.
+///
+/// # Arguments
+/// - `lines`: Lines to check.
+///
+/// # Returns
+/// - `Err`:
+///     - String: Description of the issue intended to be shown to the user.
+///     - usize: Index of the line with the problematic word.
+///     - Word: The problematic word.
+fn check_constants(lines: &[line::Line]) -> Result<(), (String, usize, &word::Word)> {
+    // Declared variables in each scope, with the index of the declaring line for constants.
+    let mut scopes: Vec<collections::HashMap<&str, Option<usize>>> = vec!(collections::HashMap::new());
+    // Scopes opened by functions.
+    let mut frames: Vec<usize> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        match &line.statement {
+            ast::Statement::BlockStart(kind, _) => {
+                let mut scope = collections::HashMap::new();
+                // Loop variable is declared in the scope of the loop.
+                if kind == "for" {
+                    if let Some(rtoken::Rtoken::Plain(name)) = line.content.get(1).map(|w| &w.rtoken) {
+                        scope.insert(&name[..], None);
+                    }
+                }
+                if kind == "fn" {
+                    frames.push(scopes.len());
+                    // Parameters shadow constants just like variables do.
+                    for pair in line.content.get(3..).unwrap_or_default().chunks(2) {
+                        if let Some(rtoken::Rtoken::Plain(name)) = pair.get(1).map(|w| &w.rtoken) {
+                            scope.insert(&name[..], None);
+                        }
+                    }
+                }
+                scopes.push(scope);
+            },
+            ast::Statement::BlockChain(_, _) if scopes.len() > 1 => *scopes.last_mut().unwrap() = collections::HashMap::new(),
+            ast::Statement::BlockEnd if scopes.len() > 1 => {
+                scopes.pop();
+                if frames.last() == Some(&scopes.len()) {
+                    frames.pop();
+                }
+            },
+            ast::Statement::Expression(Some(assign), _) => match assign {
+                assign::Assign::Dec(_, name) => { scopes.last_mut().unwrap().insert(name, None); },
+                assign::Assign::Const(_, name) => { scopes.last_mut().unwrap().insert(name, Some(i)); },
+                assign::Assign::Nondec(name) => {
+                    // Code of a function sees its own scopes and the global one.
+                    let frame = frames.last().copied().unwrap_or(0);
+                    let declared = scopes[frame..].iter().rev().chain(&scopes[..frame.min(1)])
+                        .find_map(|scope| scope.get(&name[..]));
+                    if let Some(Some(declaration)) = declared {
+                        let location = format!("{}:{}", lines[*declaration].file_name, lines[*declaration].line_number);
+                        return Err((format!("Cannot assign to constant \"{}\" declared at {}!", name.italic(), location),
+                                    i, &line.content[0]));
+                    }
+                },
+            },
+            _ => {},
+        }
+    }
+
+    Ok(())
+}
+
+/// What the interpreter should do after running a line.
+enum Flow {
+    /// Continue with the next line.
+    Next,
+    /// Jump to a lable.
+    Jump(String),
+    /// Leave the current function with a value.
+    Return(var::Var),
+}
+
 fn run_runk_line<'a> (line:      &'a line::Line,
                       info:      &source_info::SourceInfo,
                       data:      &mut program_data::ProgramData,
-                      repl_mode: bool) -> Result<Option<String>, (String, Option<word::Word>)> {
+                      repl_mode: bool) -> Result<Flow, RunkError> {
     if data.debug {
         eprint!("{}", format!("RUN {}\t| ", &info.line_number).bright_yellow());
         eprint!("{} ", line);
         eprintln!();
     }
 
-    // Splitting assignment and expression
-    let (assign, exp_start_index) = match preprocess_assignment(&line.content[..]) {
-        Ok(tuple)   => tuple,
-        Err((s, w)) => return Err((s, Some(w.clone()))),
+    let no_assign = None;
+    let (assign, is_return) = match &line.statement {
+        ast::Statement::Expression(assign, _) => (assign, false),
+        // Lines starting with "ret" return the value of the following expression.
+        ast::Statement::Return(_) => (&no_assign, true),
+        _ => return Ok(Flow::Next),
     };
     // Resolves expressions and returns a value;
-    let (ret, exp_end_index) = resolve_exp(&line.content[exp_start_index..], &info, data);
+    let chunk = line.chunk.get_or_init(|| bytecode::Chunk::new(&line.statement, data));
+    let ret = resolve_exp(chunk, data)?;
 
     // Assigns the value from the expression
     match ret.var {
         Ok(v) => {
-            // If line continues beyon what was processed indicates there are incorrect
-            // and unneeded tokens following it. (resolve_exp ends when the literal
-            // is complete or in case of functions, when the nesting stacks is empty).
-            if exp_start_index + exp_end_index < line.content.len() {
-                // Special exception for when the line ends with an unprocessed OnFunctionFail token,
-                // because checking that without processing it would be dirtier than this hack.
-                if line.content[exp_start_index + exp_end_index].rtoken != rtoken::Rtoken::OnFunctionFail {
-                    return Err((format!("Unexpected token \"{}\" after expression!", &line.content[exp_start_index+exp_end_index].original),
-                                Some(line.content[exp_start_index+exp_end_index].clone())),
-                    );
-                }
+            // Executes jump
+            match ret.jump_to {
+                Some(func_return::Jump::Lable(s)) => return Ok(Flow::Jump(s)),
+                Some(func_return::Jump::Exit(code)) => return Err(RunkError::exit(code)),
+                None => {},
             }
 
-            execute_assignment(&assign, &v, &info, data, repl_mode);
+            if is_return {
+                return Ok(Flow::Return(v));
+            }
 
-            // Executes jump
-            return match ret.jump_to {
-                Option::None    => Ok(None),
-                Option::Some(s) => Ok(Some(s)),
-            };
+            execute_assignment(assign, chunk.target, &v, info, data, repl_mode)?;
+            Ok(Flow::Next)
         },
         Err((string, opt_word)) => {
-            if opt_word.is_some() {
-                let word = opt_word.unwrap();
+            Err(RunkError::new(ErrorKind::Runtime, string, info, opt_word.as_ref()))
+        }
+    }
+}
+
+/// Lines of runk code that are beeing executed together with lables declared in them.
+struct Code<'a> {
+    /// Lines that were already parsed.
+    lines: Cow<'a, [line::Line]>,
+    lables: collections::HashMap<String, usize>,
+    /// All lables of the code are known, so a jump to an unknown one fails right away.
+    lables_complete: bool,
+    /// Buffer from which more lines are read once `lines` run out.
+    /// Code that is already fully parsed (like the body of a function) has none.
+    reader: Option<&'a mut dyn BufRead>,
+    file_name: &'a str,
+    /// Number of lines that were read from `reader`.
+    file_line_number: usize,
+    repl_mode: bool,
+    /// Already found results of `find_block_part`.
+    block_parts: collections::HashMap<usize, usize>,
+    /// Already found results of `find_block_start`.
+    block_starts: collections::HashMap<usize, Option<usize>>,
+    /// Starts of blocks that are beeing run, each of them has a scope open.
+    blocks: Vec<usize>,
+    /// Items of "<for" loops that are beeing run and the index of the next one, by start of the loop.
+    iterations: collections::HashMap<usize, (Vec<var::Var>, usize)>,
+    /// Start of a loop that is going back for its next round.
+    next_round: Option<usize>,
+}
+
+impl Code<'_> {
+    fn new<'a>(lines: Cow<'a, [line::Line]>,
+               reader: Option<&'a mut dyn BufRead>,
+               file_name: &'a str,
+               repl_mode: bool) -> Code<'a> {
+        Code {
+            lines,
+            lables: collections::HashMap::new(),
+            lables_complete: false,
+            reader,
+            file_name,
+            file_line_number: 0,
+            repl_mode,
+            block_parts: collections::HashMap::new(),
+            block_starts: collections::HashMap::new(),
+            blocks: Vec::new(),
+            iterations: collections::HashMap::new(),
+            next_round: None,
+        }
+    }
+
+    /// Makes sure the line at `index` is parsed. Returns false if the code ends before it.
+    fn load_line(&mut self, index: usize) -> Result<bool, RunkError> {
+        while self.lines.len() <= index {
+            let reader = match self.reader.as_deref_mut() {
+                Some(reader) => reader,
+                None => return Ok(false),
+            };
+
+            match parser::parse_file(reader, self.file_name, self.lines.len()+1, self.repl_mode) {
+                ParseResult::Err(err, relative_line_number, original, opt_word) => {
+                    let info = source_info::SourceInfo::new(self.file_line_number + relative_line_number, self.file_name, original);
+                    return Err(RunkError::new(ErrorKind::Parse, err, &info, opt_word.as_ref()));
+                },
+                ParseResult::Ok(mut line, relative_line_number) => {
+                    self.file_line_number += relative_line_number;
+                    if line.content.is_empty() {
+                        continue;
+                    }
+                    line.line_number = self.file_line_number;
+                    self.lines.to_mut().push(*line);
+                },
+                ParseResult::Eof => return Ok(false),
+            };
+        }
+
+        Ok(true)
+    }
+
+    /// # Description
+    /// Reads the rest of the code and registers all of its lables before it's run.
+    ///
+    /// # Returns
+    /// - `Ok`: Amount of lables found.
+    /// - `Err`: A lable was declared twice, a lable literal refers to a lable that doesn't exist
+    ///   or a constant is assigned to.
+    fn prescan_lables(&mut self) -> Result<usize, RunkError> {
+        while self.load_line(self.lines.len())? {}
+
+        match prescan_lables(&self.lines).and_then(|lables| check_constants(&self.lines).map(|_| lables)) {
+            Ok(lables) => {
+                self.lables = lables;
+                self.lables_complete = true;
+                Ok(self.lables.len())
+            },
+            Err((s, i, w)) => {
+                let info = source_info::SourceInfo::new(self.lines[i].line_number, self.file_name, self.lines[i].original.clone());
+                Err(RunkError::new(ErrorKind::Syntax, s, &info, Some(w)))
+            },
+        }
+    }
+
+    /// Finds the index of the next part of the block that has a part starting at `index`.
+    /// That is either a line like "><else" or the closing ">". Nested blocks are skipped.
+    fn find_block_part(&mut self, index: usize) -> Result<Option<usize>, RunkError> {
+        if let Some(i) = self.block_parts.get(&index) {
+            return Ok(Some(*i));
+        }
+
+        let mut depth = 0;
+        let mut i = index + 1;
+        while self.load_line(i)? {
+            match self.lines[i].statement {
+                ast::Statement::BlockStart(_, _) => depth += 1,
+                ast::Statement::BlockChain(_, _) | ast::Statement::BlockEnd if depth == 0 => {
+                    self.block_parts.insert(index, i);
+                    return Ok(Some(i));
+                },
+                ast::Statement::BlockEnd => depth -= 1,
+                _ => {},
+            }
+            i += 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Finds the index of a ">" closing the block that has a part starting at `index`.
+    fn find_block_end(&mut self, index: usize) -> Result<Option<usize>, RunkError> {
+        let mut i = index;
+        loop {
+            i = match self.find_block_part(i)? {
+                Some(i) => i,
+                None => return Ok(None),
+            };
+            if let ast::Statement::BlockEnd = self.lines[i].statement {
+                return Ok(Some(i));
+            }
+        }
+    }
+
+    /// # Description
+    /// Finds the index of a ">" closing the block that has a part starting at `index`.
+    ///
+    /// # Returns
+    /// - `Ok`: Index of the ">".
+    /// - `Err`: The code ended before the block was closed.
+    fn expect_block_end(&mut self, index: usize, info: &source_info::SourceInfo) -> Result<usize, RunkError> {
+        match self.find_block_end(index)? {
+            Some(end) => Ok(end),
+            None => Err(RunkError::new(ErrorKind::Syntax, format!("Block is missing a closing \"{}\"!", ">".italic()),
+                                       info, Some(&self.lines[index].content[0]))),
+        }
+    }
+
+    /// Finds the index of the innermost loop containing the line at `index`.
+    fn find_loop_start(&mut self, index: usize) -> Option<usize> {
+        let mut i = index;
+        loop {
+            i = self.find_block_start(i)?;
+            if self.lines[i].statement.starts_loop() {
+                return Some(i);
+            }
+        }
+    }
+
+    /// Makes sure the loop starting at `index` and closed at `end` has only one part.
+    fn expect_single_part(&mut self, index: usize, end: usize, kind: &str, info: &source_info::SourceInfo) -> Result<(), RunkError> {
+        if let Some(part) = self.find_block_part(index)? {
+            if part != end {
+                let chain = self.lines[part].content[0].clone();
+                return Err(RunkError::new(ErrorKind::Syntax, format!("Unexpected \"{}\" in a {} block!", chain.original.italic(), kind),
+                                          info, Some(&chain)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds the index of the line that opened the block which has a part starting at `index`.
+    fn find_block_start(&mut self, index: usize) -> Option<usize> {
+        if let Some(start) = self.block_starts.get(&index) {
+            return *start;
+        }
+
+        let mut depth = 0;
+        let mut start = None;
+        for i in (0..index).rev() {
+            match self.lines[i].statement {
+                ast::Statement::BlockEnd => depth += 1,
+                ast::Statement::BlockStart(_, _) if depth == 0 => {
+                    start = Some(i);
+                    break;
+                },
+                ast::Statement::BlockStart(_, _) => depth -= 1,
+                _ => {},
+            }
+        }
+
+        self.block_starts.insert(index, start);
+        start
+    }
+
+    /// Opens a scope for the block starting at `start`.
+    fn enter_block(&mut self, start: usize, data: &mut program_data::ProgramData) {
+        self.blocks.push(start);
+        data.vars.enter_scope();
+    }
 
-                return Err((string, Some(word)));
+    /// Closes the scope of the innermost block that is beeing run.
+    fn exit_block(&mut self, data: &mut program_data::ProgramData) {
+        if self.blocks.pop().is_some() {
+            data.vars.exit_scope();
+        }
+    }
+
+    /// Closes scopes of blocks that are beeing run up to and including the one starting at `start`.
+    fn exit_blocks_to(&mut self, start: usize, data: &mut program_data::ProgramData) {
+        while let Some(block) = self.blocks.pop() {
+            data.vars.exit_scope();
+            if block == start {
+                break;
             }
+        }
+    }
+
+    /// Makes the open scopes match the blocks containing the line at `index` after a jump to it.
+    /// Scopes of blocks that were left are closed and blocks that were jumped into get new ones.
+    fn sync_blocks(&mut self, index: usize, data: &mut program_data::ProgramData) {
+        let mut starts = Vec::new();
+        let mut i = index;
+        while let Some(start) = self.find_block_start(i) {
+            starts.push(start);
+            i = start;
+        }
+        starts.reverse();
 
-            return Err((string, None));
+        let common = self.blocks.iter().zip(&starts).take_while(|(a, b)| a == b).count();
+        while self.blocks.len() > common {
+            self.exit_block(data);
+        }
+        for start in starts.into_iter().skip(common) {
+            self.enter_block(start, data);
         }
     }
 }
 
 /// # Description
-/// Main way of running runk code.
+/// Resolves the value of the expression in the header of a block like "<for".
 ///
 /// # Arguments
-/// - `input_file_reader`: Source of runk source code.
-/// - `file_name`: Name of the file that is beeing executed. Used for debug only.
-/// If the data are comming from the standard input pass "<stdin>"
-/// - `data`: Runtime data of a runk program. A this time the function expects data to be uninitialized.
-/// TODO add init to data constructor.
-/// TODO make compatible with C.
-#[no_mangle]
-pub extern "C" fn run_runk_buffer(mut input_file_reader: Box<dyn BufRead>,
-                                  file_name: &str,
-                                  data: &mut program_data::ProgramData,
-                                  repl_mode: bool) {
-    let mut index            = 0;
-    let mut info             = source_info::SourceInfo::new(index, &file_name, String::new()); // TODO add text
-    let mut lines            = Vec::<line::Line>::new();
-    let mut file_line_number = 0;
-    let mut opt_jump_lab: Option<String> = None;
+/// - `line`: Header of the block.
+/// - `info`: Source information of `line`.
+/// - `data`: Runtime data of the runk program.
+///
+/// # Returns
+/// - `Ok`: Value of the expression.
+/// - `Err`: The expression is missing or failed.
+fn resolve_block_value(line: &line::Line,
+                       info: &source_info::SourceInfo,
+                       data: &mut program_data::ProgramData) -> Result<var::Var, RunkError> {
+    if data.debug {
+        eprint!("{}", format!("RUN {}\t| ", &info.line_number).bright_yellow());
+        eprint!("{} ", line);
+        eprintln!();
+    }
 
-    // match load_lables(&lines, &mut data.lables) {
-    //     Ok(n) => {
-    //         if data.debug {
-    //             eprintln!("Found {} lables", n);
-    //         }
-    //     },
-    //     Err((s, w)) => {
-    //         info.line_number = w.line;
-    //         info.original = &lines[w.parsed_line].original;
-    //         fatal_error(&info, s, Some(&w));
-    //     }
-    // }
-
-    // (Re)inicialize program data.
-    data.add_primitive_functions();
-    data.add_special_variables();
+    if line.statement.expression().is_none() {
+        return Err(RunkError::new(ErrorKind::Syntax, "Missing condition!".to_string(), info, Some(&line.content[0])));
+    }
+
+    let chunk = line.chunk.get_or_init(|| bytecode::Chunk::new(&line.statement, data));
+    let ret = resolve_exp(chunk, data)?;
+    let value = match ret.var {
+        Ok(v) => v,
+        Err((s, opt_w)) => return Err(RunkError::new(ErrorKind::Runtime, s, info, opt_w.as_ref())),
+    };
+    if let Some(func_return::Jump::Exit(code)) = ret.jump_to {
+        return Err(RunkError::exit(code));
+    }
+
+    Ok(value)
+}
+
+/// # Description
+/// Resolves the condition of a block like "<if".
+///
+/// # Arguments
+/// - `line`: Header of the block. The condition follows the first word.
+/// - `info`: Source information of `line`.
+/// - `data`: Runtime data of the runk program.
+///
+/// # Returns
+/// - `Ok`: Whether the condition was met (the value isn't zero).
+/// - `Err`: The condition couldn't be resolved.
+fn resolve_condition(line: &line::Line,
+                     info: &source_info::SourceInfo,
+                     data: &mut program_data::ProgramData) -> Result<bool, RunkError> {
+    let value = resolve_block_value(line, info, data)?;
+    let mut cond = var::Var::z(Zero::zero()).unwrap();
+    if value.fit_into(&mut cond).is_err() {
+        return Err(RunkError::new(ErrorKind::Runtime, format!("Condition has to be a number, not \"{}\"!", value),
+                                  info, Some(&line.content[1])));
+    }
+
+    Ok(cond != var::Var::z(Zero::zero()).unwrap())
+}
+
+/// # Description
+/// Runs lines of `code` until they run out or a value is returned.
+///
+/// # Arguments
+/// - `code`: Lines to run.
+/// - `data`: Runtime data of the runk program.
+/// - `in_function`: Whether `code` is a body of a function. Only functions can return.
+///
+/// # Returns
+/// - `Ok`: Value returned with "ret" or `None` if the code ran out.
+/// - `Err`: Error that stopped the code.
+fn run_runk_code(code: &mut Code, data: &mut program_data::ProgramData, in_function: bool) -> Result<Option<var::Var>, RunkError> {
+    let mut index = 0;
+    let mut opt_jump_lab: Option<String> = None;
 
-    // Read, evaluate, print loop.
     loop {
         // Try to resolve a pending jump if there is one.
         if let Some(str_lab) = opt_jump_lab.clone() {
             // It's ok if the lable wasn't found yet, we will continue parsing the file without
             // exectin it until it is found.
-            match data.lables.get(&str_lab) {
-                Some(i) => {
-                    index = *i;
-                    opt_jump_lab = None;
-                },
-                None    => {},
+            if let Some(i) = code.lables.get(&str_lab) {
+                index = *i;
+                opt_jump_lab = None;
+                code.sync_blocks(index, data);
             }
         }
-        info.line_number = file_line_number + 1;
 
         // Read and parse another line if not running through lines that were already parsed.
-        if index+1 > lines.len() || lines.len() == 0 {
-            match parser::parse_file(&mut input_file_reader, &file_name[..], index+1, repl_mode) {
-                ParseResult::Err(err, _line_num) => {
-                    fatal_error(&info, err, None);
-                },
-                ParseResult::Ok(line, relative_line_number) => {
-                    file_line_number += relative_line_number;
-                    if line.content.len() == 0 {
-                        continue;
-                    }
-                    lines.push(line);
-                },
-                ParseResult::Eof => {
-                    if let Some(str_lab) = opt_jump_lab {
-                        fatal_error(&info, format!("Lable \"{}\" not found.", str_lab.italic()),
-                                    None);
-                    }
-                    break
-                },
-            };
+        if !code.load_line(index)? {
+            if let Some(str_lab) = opt_jump_lab {
+                let info = source_info::SourceInfo::new(code.file_line_number, code.file_name, "");
+                return Err(RunkError::new(ErrorKind::Runtime, format!("Lable \"{}\" not found.", str_lab.italic()),
+                                          &info, None));
+            }
+            return Ok(None);
         }
 
-        let line = &lines[index];
-        info.original = line.original.clone();
+        let line = &code.lines[index];
+        let info = source_info::SourceInfo::new(line.line_number, code.file_name, line.original.clone());
+        let seeking = opt_jump_lab.is_some();
 
-        // Look for lable declarations
-        if line.content.len() == 1 {
-            if let rtoken::Rtoken::LableLiteral(lable) = &line.content[0].rtoken {
-                if let Some(i) = data.lables.get(&lable[..]) {
+        match &line.statement {
+            // Look for lable declarations
+            ast::Statement::Lable(lable) => {
+                if let Some(i) = code.lables.get(&lable[..]) {
                     if *i != index {
-                        fatal_error(&info, format!("Redefinition of lable \"{}\".", &lable), Some(&line.content[0]));
+                        return Err(RunkError::new(ErrorKind::Syntax, format!("Redefinition of lable \"{}\".", &lable),
+                                                  &info, Some(&line.content[0])));
                     }
                 }
 
-            data.lables.insert(lable.clone(), index);
-            }
+                code.lables.insert(lable.clone(), index);
+            },
+            // Functions are defined as a whole and skipped even when seeking a lable.
+            ast::Statement::BlockStart(kind, _) if kind == "fn" => {
+                let header = line.content.clone();
+                let end = code.expect_block_end(index, &info)?;
+
+                if !seeking {
+                    let body = code.lines[index+1..end].to_vec();
+                    let mut user_func = match user_func::UserFunc::new(&header, body) {
+                        Ok(f) => f,
+                        Err((s, w)) => return Err(RunkError::new(ErrorKind::Syntax, s, &info, Some(w))),
+                    };
+                    // Whole body is known, so its lables can be found right away.
+                    let checked = load_lables(&user_func.body, &mut user_func.lables)
+                        .and_then(|_| check_constants(&user_func.body));
+                    if let Err((s, i, w)) = checked {
+                        let body_line = &user_func.body[i];
+                        let body_info = source_info::SourceInfo::new(body_line.line_number, code.file_name, body_line.original.clone());
+                        return Err(RunkError::new(ErrorKind::Syntax, s, &body_info, Some(w)));
+                    }
+                    if data.funcs.get(&user_func.name).is_some_and(func::Func::is_builtin) {
+                        return Err(RunkError::new(ErrorKind::Runtime,
+                                                  format!("Cannot redefine built-in function \"{}\"!", user_func.name.italic()),
+                                                  &info, Some(&header[2])));
+                    }
+                    data.funcs.insert(user_func.name.clone(), user_func.into_func());
+                }
+
+                index = end + 1;
+                continue;
+            },
+            // Other blocks are entered line by line when seeking a lable.
+            _ if seeking => {},
+            // Runs the first part of a block whose condition is met. Parts are separated by
+            // lines like "><elif" and the last one may be "><else".
+            ast::Statement::BlockStart(kind, _) if kind == "if" => {
+                // Whole block has to be read before it's run.
+                code.expect_block_end(index, &info)?;
+
+                // Parts after "><else" could never run.
+                let mut part = index;
+                while let Some(next) = code.find_block_part(part)? {
+                    let next_line = &code.lines[next];
+                    if let ast::Statement::BlockEnd = next_line.statement {
+                        break;
+                    }
+                    if let ast::Statement::BlockChain(_, None) = code.lines[part].statement {
+                        let chain = next_line.content[0].clone();
+                        let chain_info = source_info::SourceInfo::new(next_line.line_number, code.file_name, next_line.original.clone());
+                        return Err(RunkError::new(ErrorKind::Syntax, format!("Unexpected \"{}\" after an else part!", chain.original.italic()),
+                                                  &chain_info, Some(&chain)));
+                    }
+                    part = next;
+                }
+
+                let mut part = index;
+                loop {
+                    let part_line = &code.lines[part];
+                    let part_info = source_info::SourceInfo::new(part_line.line_number, code.file_name, part_line.original.clone());
+                    let enter = match &part_line.statement {
+                        ast::Statement::BlockChain(_, None) => true,
+                        _ => resolve_condition(part_line, &part_info, data)?,
+                    };
+
+                    if enter {
+                        code.enter_block(index, data);
+                        index = part + 1;
+                        break;
+                    }
+
+                    part = match code.find_block_part(part)? {
+                        Some(p) => p,
+                        None => return Err(RunkError::new(ErrorKind::Syntax, format!("Block is missing a closing \"{}\"!", ">".italic()),
+                                                          &info, Some(&code.lines[index].content[0]))),
+                    };
+                    if let ast::Statement::BlockEnd = code.lines[part].statement {
+                        index = part + 1;
+                        break;
+                    }
+                }
+                continue;
+            },
+            // Runs the lines inside the block for as long as the condition is met.
+            ast::Statement::BlockStart(kind, _) if kind == "while" => {
+                let end = code.expect_block_end(index, &info)?;
+                code.expect_single_part(index, end, "while", &info)?;
+
+                // Every round of the loop has a scope of its own.
+                index = match resolve_condition(&code.lines[index], &info, data)? {
+                    true => {
+                        code.enter_block(index, data);
+                        index + 1
+                    },
+                    false => end + 1,
+                };
+                continue;
+            },
+            // Runs the lines inside the block once for every item of the list.
+            ast::Statement::BlockStart(kind, _) if kind == "for" => {
+                let end = code.expect_block_end(index, &info)?;
+                code.expect_single_part(index, end, "for", &info)?;
+
+                // Items are resolved only once, when the loop is entered.
+                if code.next_round.take() != Some(index) || !code.iterations.contains_key(&index) {
+                    let items = match resolve_block_value(&code.lines[index], &info, data)? {
+                        var::Var::A(items) => items,
+                        other => return Err(RunkError::new(ErrorKind::Runtime, format!("Items of a loop have to be a list, not \"{}\"!", other),
+                                                           &info, Some(&code.lines[index].content[2]))),
+                    };
+                    code.iterations.insert(index, (items, 0));
+                }
+
+                let (items, next) = code.iterations.get_mut(&index).unwrap();
+                let item = items.get(*next).cloned();
+                *next += 1;
+                // Every round of the loop has a scope of its own with the item declared in it.
+                index = match item {
+                    Some(item) => {
+                        let name = code.lines[index].content[1].original.clone();
+                        code.enter_block(index, data);
+                        data.vars.insert(name, item);
+                        index + 1
+                    },
+                    None => {
+                        code.iterations.remove(&index);
+                        end + 1
+                    },
+                };
+                continue;
+            },
+            ast::Statement::Break | ast::Statement::Continue => {
+                let keyword = line.content[0].clone();
+                let is_continue = matches!(line.statement, ast::Statement::Continue);
+                let start = match code.find_loop_start(index) {
+                    Some(start) => start,
+                    None => return Err(RunkError::new(ErrorKind::Syntax,
+                                                      format!("Unexpected \"{}\" outside of a loop!", keyword.original.italic()),
+                                                      &info, Some(&keyword))),
+                };
+                code.exit_blocks_to(start, data);
+                index = match is_continue {
+                    // Loop will be left after checking the condition again.
+                    true => {
+                        code.next_round = Some(start);
+                        start
+                    },
+                    false => {
+                        code.iterations.remove(&start);
+                        code.find_block_end(start)?.unwrap() + 1
+                    },
+                };
+                continue;
+            },
+            // Reaching the next part of a block means the current part is done.
+            ast::Statement::BlockChain(_, _) => {
+                let chain = line.content[0].clone();
+                match code.find_block_start(index) {
+                    Some(start) if code.lines[start].statement.starts_block("if") => {},
+                    _ => return Err(RunkError::new(ErrorKind::Syntax,
+                                                   format!("Unexpected \"{}\" outside of an if block!", chain.original.italic()),
+                                                   &info, Some(&chain))),
+                }
+                code.exit_block(data);
+                index = code.expect_block_end(index, &info)? + 1;
+                continue;
+            },
+            ast::Statement::BlockEnd => {
+                let end = line.content[0].clone();
+                index = match code.find_block_start(index) {
+                    // Going back to check the condition of a loop or to take its next item.
+                    Some(start) if code.lines[start].statement.starts_loop() => {
+                        code.next_round = Some(start);
+                        start
+                    },
+                    Some(_) => index + 1,
+                    None => return Err(RunkError::new(ErrorKind::Syntax, format!("Unexpected \"{}\" outside of a block!", ">".italic()),
+                                                      &info, Some(&end))),
+                };
+                code.exit_block(data);
+                continue;
+            },
+            _ => {},
         }
 
+        let line = &code.lines[index];
+
         // Run current line if we're currently not seeking a lable to jump to.
-        if opt_jump_lab.is_none() {
-            match run_runk_line(&lines[index], &info, data, repl_mode){
-                Ok(opt_str_lab)         => { opt_jump_lab = opt_str_lab },
-                Err((string, opt_word)) => {
-                    if let Some(w) = opt_word {
-                        fatal_error(&info, string, Some(&w));
+        if !seeking {
+            match run_runk_line(line, &info, data, code.repl_mode)? {
+                Flow::Next          => {},
+                Flow::Jump(str_lab) => {
+                    if code.lables_complete && !code.lables.contains_key(&str_lab) {
+                        return Err(RunkError::new(ErrorKind::Runtime, format!("Lable \"{}\" not found.", str_lab.italic()),
+                                                  &info, None));
+                    }
+                    opt_jump_lab = Some(str_lab)
+                },
+                Flow::Return(v)     => {
+                    if !in_function {
+                        return Err(RunkError::new(ErrorKind::Syntax, "Cannot return outside of a function!".to_string(),
+                                                  &info, Some(&line.content[0])));
                     }
-                    fatal_error(&info, string, None);
+                    return Ok(Some(v));
                 },
             };
         }
@@ -329,9 +898,245 @@ pub extern "C" fn run_runk_buffer(mut input_file_reader: Box<dyn BufRead>,
         // Move to the next line.
         index += 1;
     }
+}
+
+/// # Description
+/// Calls a function defined in runk source code. The function gets a scope of its own
+/// with its parameters declared in it. Variables of the caller are hidden from it,
+/// only the global ones are visible.
+///
+/// # Arguments
+/// - `user_func`: Function to call.
+/// - `args`: Arguments already converted to the types of the function's parameters.
+/// - `data`: Runtime data of the runk program.
+///
+/// # Returns
+/// - `Ok`: Struct with the value returned by the function.
+/// - `Err`: Error that stopped the body of the function.
+pub fn run_runk_function(user_func: &user_func::UserFunc,
+                         args: &[var::Var],
+                         data: &mut program_data::ProgramData) -> Result<func_return::FuncReturn, RunkError> {
+    if data.call_depth >= data.max_call_depth {
+        return Ok(func_return::FuncReturn::func_error(
+            format!("Calls of functions are nested deeper than {}!", data.max_call_depth)));
+    }
+    data.call_depth += 1;
+    data.vars.enter_function();
+    for ((name, _dtype), arg) in user_func.params.iter().zip(args) {
+        data.vars.insert(name.clone(), arg.clone());
+    }
+
+    let file_name = match user_func.body.first() {
+        Some(line) => line.file_name.clone(),
+        None => String::new(),
+    };
+    let mut code = Code::new(Cow::Borrowed(&user_func.body[..]), None, &file_name, false);
+    code.lables = user_func.lables.clone();
+    code.lables_complete = true;
+    let returned = run_runk_code(&mut code, data, true);
+    data.call_depth -= 1;
+    let returned = match returned {
+        Ok(r) => r,
+        // Failing line fails the call just like a failing primitive does, so the caller can handle it.
+        Err(e) if e.kind == ErrorKind::Runtime => {
+            data.vars.exit_function();
+            return Ok(func_return::FuncReturn::func_error(format!("{} (in function \"{}\" at {}:{})",
+                                                                  e.message, user_func.name.italic(), e.file_name, e.line_number)));
+        },
+        // Other errors stop the program. Scopes are left open, so the state where it happened
+        // can be shown. They are closed once the error reaches the top.
+        Err(e) => return Err(e),
+    };
+    data.vars.exit_function();
+
+    Ok(match returned {
+        Some(v) => {
+            let mut ret = user_func.ret.clone();
+            match v.fit_into(&mut ret) {
+                Ok(_) => func_return::FuncReturn {
+                    var: Ok(ret),
+                    jump_to: None,
+                },
+                Err(e) => func_return::FuncReturn::func_error(e),
+            }
+        },
+        None => func_return::FuncReturn::func_error(
+            format!("Function \"{}\" ended without returning a value!", user_func.name.italic())),
+    })
+}
+
+/// # Description
+/// Main way of running runk code.
+///
+/// # Arguments
+/// - `input_file_reader`: Source of runk source code.
+/// - `file_name`: Name of the file that is beeing executed. Used for debug only.
+///   If the data are comming from the standard input pass "<stdin>"
+/// - `data`: Runtime data of a runk program.
+/// - `repl_mode`: Whether to show a prompt before reading each line.
+///
+/// # Returns
+/// - `Ok`: The code ran out.
+/// - `Err`: Error that stopped the code. A call to "exit" is reported as an error of kind `ErrorKind::Exit`.
+pub fn run_runk_buffer(input_file_reader: &mut dyn BufRead,
+                       file_name: &str,
+                       data: &mut program_data::ProgramData,
+                       repl_mode: bool) -> Result<(), RunkError> {
+    // Read, evaluate, print loop.
+    // Lables are indexes into lines of this buffer so the ones from previous runs can't be reused.
+    let mut code = Code::new(Cow::Owned(Vec::new()), Some(input_file_reader), file_name, repl_mode);
+    if data.prescan_lables {
+        let n = code.prescan_lables()?;
+        if data.debug {
+            eprintln!("Found {} lables", n);
+        }
+    }
+    let result = data.with_decimal_context(|data| run_runk_code(&mut code, data, false));
+    data.lables = code.lables;
+    if let Err(e) = &result {
+        if data.debug && !matches!(e.kind, ErrorKind::Exit(_)) {
+            eprintln!("{}", "STOPPED".red());
+            data.debug_status();
+        }
+    }
+    // Only the global scope outlives the code.
+    data.vars.reset();
+    result?;
 
     if data.debug {
         eprintln!("{}", "DONE".green());
         data.debug_status();
     }
+
+    Ok(())
+}
+
+
+// ==========
+// Unit tests
+// ==========
+#[test]
+fn test_user_functions() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Nat x: 5\n<fn Int add Int a Int b\n    ret [$a + $b]\n>\n<fn Nat fact Nat n\n    <if [$n = 0]\n        ret 1\n    >\n\
+                         ret [$n * (fact [$n - 1])]\n>\nInt s: (add -2 $x)\nNat f: (fact 5)\n").unwrap();
+    assert_eq!(interpreter.get_var("s"), Some(&var::Var::new(3)));
+    assert_eq!(interpreter.get_var("f"), Some(&var::Var::new(120)));
+
+    // Variables of the caller are hidden from the function.
+    let error = interpreter.run_str("<fn Nat peek\n    ret $local\n>\n<if 1\n    Nat local: 1\n    Nat p: (peek)\n>\n").unwrap_err();
+    assert!(error.message.contains("in function"));
+    let error = interpreter.run_str("<fn Nat none\n    Nat a: 1\n>\nNat n: (none)\n").unwrap_err();
+    assert!(error.message.contains("ended without returning a value"));
+    assert_eq!(interpreter.run_str("<fn Nat line\n    ret 1\n>\n").unwrap_err().kind, ErrorKind::Runtime);
+    assert_eq!(interpreter.run_str("ret 1\n").unwrap_err().kind, ErrorKind::Syntax);
+}
+
+#[test]
+fn test_function_errors() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("<fn Nat bad Nat n\n    ret (/ $n 0)\n>\nNat r: (bad 1) else (+ 7)\n").unwrap();
+    assert_eq!(interpreter.get_var("r"), Some(&var::Var::new(7)));
+
+    // Uncaught error points to the call and tells where in the function it happened.
+    let error = interpreter.run_str("Nat e: (bad 1)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Runtime);
+    assert_eq!((error.line_number, error.column), (1, Some(8)));
+    assert!(error.message.contains("Division by zero") && error.message.contains("<string>:2"));
+    // Exit still ends the program from inside of a function.
+    let error = interpreter.run_str("<fn Nat quit\n    (exit 3)\n    ret 1\n>\nNat q: (quit) else (+ 1)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Exit(3));
+}
+
+#[test]
+fn test_infix_expressions() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Int x: -4\nInt a: [10 + $x * 3 - -2]\nNat b: [(1 + 2) * 3 = 9 and $x < 0]\nInt c: [(+ 1 2) * [7 / 2]]\n").unwrap();
+    assert_eq!(interpreter.get_var("a"), Some(&var::Var::new(0)));
+    assert_eq!(interpreter.get_var("b"), Some(&var::Var::new(1)));
+    assert_eq!(interpreter.get_var("c"), Some(&var::Var::new(9)));
+
+    // Failing operator is reported like a failing call.
+    let error = interpreter.run_str("Int d: [1 + 2 / 0]\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Runtime);
+    assert_eq!(error.column, Some(14));
+}
+
+#[test]
+fn test_if_blocks() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Nat x: 0\n<if 0\n    x: 1\n><elif 1\n    x: 2\n><else\n    x: 3\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("x"), Some(&var::Var::new(2)));
+    interpreter.run_str("<if [$x > 1]\n    <if 0\n        x: 4\n    ><else\n        x: [$x * 10]\n    >\n    x: [$x + 1]\n><else\n    x: 5\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("x"), Some(&var::Var::new(21)));
+    interpreter.run_str("<if 0\n    x: 1\n><elif 0\n    x: 2\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("x"), Some(&var::Var::new(21)));
+
+    // Nothing can follow the else part.
+    let error = interpreter.run_str("<if 0\n    x: 1\n><else\n    x: 2\n><elif 1\n    x: 3\n>\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Syntax);
+    assert_eq!((error.line_number, error.column), (5, Some(0)));
+    assert!(error.message.contains("after an else part"));
+    assert_eq!(interpreter.run_str("<if 1\n    x: 1\n><else\n    x: 2\n><else\n    x: 3\n>\n").unwrap_err().kind, ErrorKind::Syntax);
+    assert_eq!(interpreter.get_var("x"), Some(&var::Var::new(21)));
+
+    let error = interpreter.run_str("<if \"yes\"\n    x: 1\n>\n").unwrap_err();
+    assert!(error.message.contains("Condition has to be a number"));
+    assert_eq!(interpreter.run_str("<if 1\n    x: 1\n").unwrap_err().kind, ErrorKind::Syntax);
+    assert_eq!(interpreter.run_str("><else\n").unwrap_err().kind, ErrorKind::Syntax);
+}
+
+#[test]
+fn test_while_loops() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Nat i: 0\nNat sum: 0\n<while [$i < 10]\n    i: [$i + 1]\n    <if [$i % 2 = 0]\n        continue\n    >\n\
+                         <if [$i > 7]\n        break\n    >\n    sum: [$sum + $i]\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("i"), Some(&var::Var::new(9)));
+    assert_eq!(interpreter.get_var("sum"), Some(&var::Var::new(16)));
+
+    // Break leaves only the innermost loop.
+    interpreter.run_str("Nat rows: 0\nNat cells: 0\n<while [$rows < 3]\n    rows: [$rows + 1]\n    <while 1\n        cells: [$cells + 1]\n\
+                         break\n    >\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("cells"), Some(&var::Var::new(3)));
+    interpreter.run_str("<while 0\n    (exit 1)\n>\n").unwrap();
+
+    let error = interpreter.run_str("Nat a: 1\n  break\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Syntax);
+    assert_eq!((error.line_number, error.column), (2, Some(2)));
+    let error = interpreter.run_str("<while 0\n    Nat b: 1\n><else\n>\n").unwrap_err();
+    assert_eq!((error.kind, error.line_number), (ErrorKind::Syntax, 1));
+    assert!(error.message.contains("in a while block"));
+    // Loops of a function can't be left from a function it calls.
+    let error = interpreter.run_str("<fn Nat leave\n    break\n>\n<while 1\n    Nat c: (leave)\n>\n").unwrap_err();
+    assert_eq!((error.kind, error.line_number), (ErrorKind::Syntax, 2));
+}
+
+#[test]
+fn test_for_loops() {
+    let mut interpreter = Interpreter::new();
+    interpreter.run_str("Nat sum: 0\nLst items: {1 2 3 4 5 6}\n<for item $items\n    <if [$item = 2]\n        continue\n    >\n\
+                         <if [$item > 4]\n        break\n    >\n    sum: [$sum + $item]\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("sum"), Some(&var::Var::new(8)));
+    // Loop variable exists only inside of the loop.
+    assert_eq!(interpreter.get_var("item"), None);
+
+    // List is resolved once, changing it doesn't change the rounds.
+    interpreter.run_str("Lst seen: {}\n<for x $items\n    items: {}\n    seen: (push $seen $x)\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("seen").unwrap().plain_string(), "{1 2 3 4 5 6}");
+    // Nested loops over the same list and a loop inside of a recursive function.
+    interpreter.run_str("Nat pairs: 0\n<for a {1 2 3}\n    <for b {1 2 3}\n        <if [$a < $b]\n            pairs: [$pairs + 1]\n        >\n    >\n>\n\
+                         <fn Nat total Lst l\n    Nat t: 0\n    <for x $l\n        t: [$t + $x]\n    >\n    ret $t\n>\n\
+                         Nat t: (total {1 (total {2 3}) 4})\n<for nothing {}\n    (exit 1)\n>\n").unwrap();
+    assert_eq!(interpreter.get_var("pairs"), Some(&var::Var::new(3)));
+    assert_eq!(interpreter.get_var("t"), Some(&var::Var::new(10)));
+
+    let error = interpreter.run_str("Nat n: 5\n<for x $n\n>\n").unwrap_err();
+    assert_eq!((error.kind, error.line_number, error.column), (ErrorKind::Runtime, 2, Some(7)));
+    assert!(error.message.contains("have to be a list"));
+    let error = interpreter.run_str("<for 1 {1}\n>\n").unwrap_err();
+    assert_eq!((error.kind, error.column), (ErrorKind::Parse, Some(5)));
+    let error = interpreter.run_str("<for x\n>\n").unwrap_err();
+    assert!(error.message.contains("Missing list"));
+    let error = interpreter.run_str("<for x {1}\n><else\n>\n").unwrap_err();
+    assert!(error.message.contains("in a for block"));
 }
@@ -7,7 +7,8 @@ use colored::Colorize;
 #[cfg(target_family = "unix")]
 use isatty::stdin_isatty;
 
-use runk::structs::program_data;
+use runk::Interpreter;
+use runk::structs::runk_error::ErrorKind;
 
 fn platform_eof_key() -> String {
         #[cfg(target_family = "unix")]
@@ -71,7 +72,7 @@ fn main() {
     let input_file_name = args.iter().nth(1);
 
     let mut is_input_stdin = false;
-    let input_file_reader: Box<dyn BufRead> = match input_file_name {
+    let mut input_file_reader: Box<dyn BufRead> = match input_file_name {
         None => {
             is_input_stdin = true;
             Box::new(BufReader::new(io::stdin()))
@@ -89,22 +90,37 @@ fn main() {
         }
     };
 
-    let mut program_data = program_data::ProgramData::new(debug.clone());
-    let mut repl_mode = false;
+    let mut interpreter = Interpreter::new();
+    interpreter.set_debug(debug);
+    // Standard input may also be used by the program itself, so it's read line by line.
+    interpreter.set_prescan_lables(!is_input_stdin);
     #[cfg(target_family = "unix")]
-    {repl_mode = is_input_stdin && stdin_isatty();}
+    let repl_mode = is_input_stdin && stdin_isatty();
+    #[cfg(not(target_family = "unix"))]
+    let repl_mode = false;
 
     if repl_mode {
         print_repl_banner();
     }
 
-    runk::run_runk_buffer(input_file_reader,
-                 match &input_file_name {
-                     None => "<stdin>",
-                     Some(file) => &file[..],
-                 },
-                 &mut program_data,
-                 repl_mode,
+    let file_name = match &input_file_name {
+        None => "<stdin>",
+        Some(file) => &file[..],
+    };
 
-    );
+    // The REPL keeps reading lines after an error, a file is stopped by it.
+    loop {
+        match interpreter.run_reader(&mut input_file_reader, file_name, repl_mode) {
+            Ok(_) => break,
+            Err(e) => match e.kind {
+                ErrorKind::Exit(code) => process::exit(code),
+                _ => {
+                    runk::prints::print_error(&e);
+                    if !repl_mode {
+                        process::exit(1);
+                    }
+                },
+            },
+        }
+    }
 }
@@ -1,18 +1,18 @@
 pub mod rtoken;
-use std::io::BufRead;
+pub mod ast;
+use std::io::{ self, BufRead };
+use std::rc::Rc;
 use crate::structs::{ word, line };
-use num_bigint::BigInt;
-use num_traits::Zero;
+use num_traits::{ Zero, One };
 use crate::structs::var;
 use colored::Colorize;
 
 fn is_special_operator(c: &char) -> bool {
-    "(:".chars().any(|s| s.eq(c))
+    "(:[{".chars().any(|s| s.eq(c))
 }
 
 enum PushString {
     Yes,
-    No,
     Separately,
 }
 
@@ -38,16 +38,11 @@ fn parse_string(s: &str) -> Option<(rtoken::Rtoken, Option<OpCancel>)> {
     }
 
     // Numeric literals
-    if let Ok(_bi) = s.parse::<BigInt>() {
+    if let Some(Ok(_var)) = var::Var::num_literal(s) {
         return Some((rtoken::Rtoken::NumLiteral(s.to_string()), None));
     }
 
     if s.len() >= 2 {
-        // Text literal
-        if s.chars().nth(0).unwrap() == '"' && s.chars().last().unwrap() == '"' {
-            return Some((rtoken::Rtoken::TextLiteral(s[1..s.len()-1].to_string()), None));
-        }
-
         // Lable literals
         if s.chars().nth(0).unwrap() == '!' {
             return Some((rtoken::Rtoken::LableLiteral(s[1..].to_string()), None));
@@ -68,14 +63,43 @@ fn parse_string(s: &str) -> Option<(rtoken::Rtoken, Option<OpCancel>)> {
                             }))
     )}
     if s == ")"  { return Some((rtoken::Rtoken::FunctionEnd, None)) }
+    if s == "["  { return Some((rtoken::Rtoken::InfixStart,
+                            Some(OpCancel {
+                                string: "]".to_string(),
+                                push_string: PushString::Separately,
+                                acc_literally: false,
+                            }))
+    )}
+    if s == "]"  { return Some((rtoken::Rtoken::InfixEnd, None)) }
+    if s == "{"  { return Some((rtoken::Rtoken::ListStart,
+                            Some(OpCancel {
+                                string: "}".to_string(),
+                                push_string: PushString::Separately,
+                                acc_literally: false,
+                            }))
+    )}
+    if s == "}"  { return Some((rtoken::Rtoken::ListEnd, None)) }
     if s == "else" { return Some((rtoken::Rtoken::OnFunctionFail, None)) }
+    if s == "ret" { return Some((rtoken::Rtoken::Return, None)) }
+    if s == "break" { return Some((rtoken::Rtoken::Break, None)) }
+    if s == "continue" { return Some((rtoken::Rtoken::Continue, None)) }
+    if s == "const" { return Some((rtoken::Rtoken::Const, None)) }
+
+    // Block start, a "<" followed by the kind of the block. Ex: "<fn"
+    if s.len() >= 2 && s.starts_with('<') && s[1..].chars().all(|c| c.is_alphabetic()) {
+        return Some((rtoken::Rtoken::BlockStart(s[1..].to_string()), None));
+    }
+    // Next part of a block, "><" followed by the kind of the part. Ex: "><else"
+    if s.len() >= 3 && s.starts_with("><") && s[2..].chars().all(|c| c.is_alphabetic()) {
+        return Some((rtoken::Rtoken::BlockChain(s[2..].to_string()), None));
+    }
 
     // Data types
     // Integer
     if s == "Int" {
         return Some(
             (
-                rtoken::Rtoken::DataType(var::Var::z(Zero::zero()).unwrap()),
+                rtoken::Rtoken::DataType(Box::new(var::Var::z(Zero::zero()).unwrap())),
                 None,
             )
     )}
@@ -83,7 +107,23 @@ fn parse_string(s: &str) -> Option<(rtoken::Rtoken, Option<OpCancel>)> {
     if s == "Nat" {
         return Some(
             (
-                rtoken::Rtoken::DataType(var::Var::n(Zero::zero()).unwrap()),
+                rtoken::Rtoken::DataType(Box::new(var::Var::n(Zero::zero()).unwrap())),
+                None,
+            )
+    )}
+    // Rational number
+    if s == "Rat" {
+        return Some(
+            (
+                rtoken::Rtoken::DataType(Box::new(var::Var::q(Zero::zero(), One::one()).unwrap())),
+                None,
+            )
+    )}
+    // Decimal number
+    if s == "Dec" {
+        return Some(
+            (
+                rtoken::Rtoken::DataType(Box::new(var::Var::d(Zero::zero(), 0).unwrap())),
                 None,
             )
     )}
@@ -91,7 +131,7 @@ fn parse_string(s: &str) -> Option<(rtoken::Rtoken, Option<OpCancel>)> {
     if s == "Txt" {
         return Some(
             (
-                rtoken::Rtoken::DataType(var::Var::t("".to_string()).unwrap()),
+                rtoken::Rtoken::DataType(Box::new(var::Var::t("".to_string()).unwrap())),
                 None,
             )
     )}
@@ -99,7 +139,23 @@ fn parse_string(s: &str) -> Option<(rtoken::Rtoken, Option<OpCancel>)> {
     if s == "Lab" {
         return Some(
             (
-                rtoken::Rtoken::DataType(var::Var::l("".to_string()).unwrap()),
+                rtoken::Rtoken::DataType(Box::new(var::Var::l("".to_string()).unwrap())),
+                None,
+            )
+    )}
+    // List
+    if s == "Lst" {
+        return Some(
+            (
+                rtoken::Rtoken::DataType(Box::new(var::Var::a(Vec::new()).unwrap())),
+                None,
+            )
+    )}
+    // Map
+    if s == "Map" {
+        return Some(
+            (
+                rtoken::Rtoken::DataType(Box::new(var::Var::m(Default::default()).unwrap())),
                 None,
             )
     )}
@@ -107,17 +163,208 @@ fn parse_string(s: &str) -> Option<(rtoken::Rtoken, Option<OpCancel>)> {
     return Some((rtoken::Rtoken::Plain(s.to_string()), None));
 }
 
-pub enum ParseResult<'a> {
+/// # Description
+/// Creates a text literal token from the text between its quotes. Variable references
+/// like `$name` and expressions like `$(+ 1 2)` or `$[1 + 2]` make it an interpolated text.
+/// Escape sequences (see `parse_escape_sequence`) are decoded, `\\$` is a plain dollar sign.
+///
+/// # Arguments
+/// - `s`: Text between the quotes.
+/// - `column`: Column of the opening quote.
+/// - `line_number`: Line number used for words of the embedded expressions.
+///
+/// # Returns
+/// - `Ok`: `TextLiteral` or `InterpolatedText` rtoken.
+/// - `Err`:
+///     - String: Description of the issue intended to be shown to the user.
+///     - usize: Index of the first char of the problematic part in `s`.
+///     - usize: Length of the problematic part in chars.
+fn parse_text_literal(s: &str, column: usize, line_number: usize) -> Result<rtoken::Rtoken, (String, usize, usize)> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut parts = Vec::<rtoken::TextPart>::new();
+    let mut text = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '\\' {
+            let (opt_c, length) = parse_escape_sequence(&chars[i..]).map_err(|(e, length)| (e, i, length))?;
+            if let Some(c) = opt_c {
+                text.push(c);
+            }
+            i += length;
+            continue;
+        }
+        if chars[i] != '$' {
+            text.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        // Column of the dollar sign in the source, the literal can continue on following lines.
+        let part_column = match chars[..i].iter().rposition(|c| *c == '\n') {
+            Some(newline) => i - newline - 1,
+            None => column + 1 + i,
+        };
+        match chars.get(i+1) {
+            // Embedded expression
+            Some('(') | Some('[') => {
+                let mut depth = 0;
+                let mut end = None;
+                for (j, c) in chars.iter().enumerate().skip(i+1) {
+                    match c {
+                        '(' | '[' => depth += 1,
+                        ')' | ']' => depth -= 1,
+                        _ => continue,
+                    }
+                    if depth == 0 {
+                        end = Some(j);
+                        break;
+                    }
+                }
+                let end = match end {
+                    Some(end) => end,
+                    None => return Err(("Embedded expression is missing a closing bracket!".to_string(), i, 2)),
+                };
+
+                // Quotes of literals inside of the expression have to be escaped.
+                let source: String = chars[i+1..=end].iter().collect::<String>()
+                    .replace("\\\"", "\"");
+                let words = parse_embedded_expression(&source, part_column + 1, line_number)
+                    .map_err(|e| (e, i, end+1-i))?;
+                if !text.is_empty() {
+                    parts.push(rtoken::TextPart::Text(text));
+                    text = String::new();
+                }
+                parts.push(rtoken::TextPart::Expression(words));
+                i = end + 1;
+            },
+            // Variable reference
+            Some(c) if c.is_alphanumeric() || *c == '_' => {
+                let mut end = i + 1;
+                while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+
+                let name: String = chars[i+1..end].iter().collect();
+                if !text.is_empty() {
+                    parts.push(rtoken::TextPart::Text(text));
+                    text = String::new();
+                }
+                parts.push(rtoken::TextPart::Expression(vec!(word::Word {
+                    rtoken: rtoken::Rtoken::VariableReference(name.clone()),
+                    original: format!("${}", name),
+                    column: part_column,
+                    line: line_number,
+                    parsed_line: line_number,
+                })));
+                i = end;
+            },
+            // Lone dollar sign
+            _ => {
+                text.push('$');
+                i += 1;
+            },
+        }
+    }
+
+    if parts.is_empty() {
+        return Ok(rtoken::Rtoken::TextLiteral(text));
+    }
+    if !text.is_empty() {
+        parts.push(rtoken::TextPart::Text(text));
+    }
+
+    Ok(rtoken::Rtoken::InterpolatedText(parts))
+}
+
+/// # Description
+/// Decodes an escape sequence in a text literal. Supported sequences are `\\n`, `\\t`, `\\r`, `\\0`,
+/// `\\\\`, `\\"`, `\\$` and `\\u{...}` with a hexadecimal code of a unicode character.
+/// Backslash at the end of a line joins it with the next one.
+///
+/// # Arguments
+/// - `chars`: Chars of the literal starting with the backslash.
+///
+/// # Returns
+/// - `Ok`:
+///     - Option<char>: Decoded char, `None` for sequences that produce nothing.
+///     - usize: Length of the sequence.
+/// - `Err`:
+///     - String: Description of the issue intended to be shown to the user.
+///     - usize: Length of the invalid sequence.
+fn parse_escape_sequence(chars: &[char]) -> Result<(Option<char>, usize), (String, usize)> {
+    let c = match chars.get(1) {
+        Some(c) => *c,
+        None => return Err(("Escape sequence is missing a character!".to_string(), 1)),
+    };
+
+    let decoded = match c {
+        'n'  => '\n',
+        't'  => '\t',
+        'r'  => '\r',
+        '0'  => '\0',
+        '\\' => '\\',
+        '"'  => '"',
+        '$'  => '$',
+        '\n' => return Ok((None, 2)),
+        'u'  => {
+            let end = match chars.iter().position(|c| *c == '}') {
+                Some(end) if chars.get(2) == Some(&'{') => end,
+                _ => return Err((format!("Unicode escape sequence has to look like \"{}\"!", "\\u{1F600}".italic()), 2)),
+            };
+            let hex: String = chars[3..end].iter().collect();
+            return match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                Some(c) if hex.len() <= 6 => Ok((Some(c), end+1)),
+                _ => Err((format!("\"{}\" is not a valid unicode character!", hex.italic()), end+1)),
+            };
+        },
+        _ => {
+            let sequence: String = chars[..2].iter().collect();
+            return Err((format!("Unknown escape sequence \"{}\"!", sequence.italic()), 2));
+        },
+    };
+
+    Ok((Some(decoded), 2))
+}
+
+/// # Description
+/// Tokenizes an expression embedded in a text literal.
+///
+/// # Arguments
+/// - `source`: Source code of the expression.
+/// - `column`: Column where the expression starts.
+/// - `line_number`: Line number used for the words.
+///
+/// # Returns
+/// - `Ok`: Words of the expression.
+/// - `Err`: Description of the issue intended to be shown to the user.
+fn parse_embedded_expression(source: &str, column: usize, line_number: usize) -> Result<Vec<word::Word>, String> {
+    let mut reader = io::Cursor::new(format!("{}\n", source));
+    match parse_file(&mut reader, "", line_number, false) {
+        ParseResult::Ok(mut line, _) => {
+            for word in line.content.iter_mut() {
+                word.column += column;
+            }
+            Ok(line.content)
+        },
+        ParseResult::Eof => Err(format!("Embedded expression \"{}\" is empty!", source.italic())),
+        ParseResult::Err(e, _, _, _) => Err(e),
+    }
+}
+
+pub enum ParseResult {
     /// Line, line number.
-    Ok(line::Line<'a>, usize),
+    Ok(Box<line::Line>, usize),
     /// File ended nothing read.
     Eof,
-    /// Description of the error and a line number on whitch it occoured.
-    Err(String, usize),
+    /// Description of the error, number of the line on whitch it occoured (relative to the
+    /// first line read), source of that line and optionally the problematic word.
+    Err(String, usize, String, Option<word::Word>),
 }
 
 /// # Description
-/// Reads runk source code from a buffer and returns a tokenized output.
+/// Reads runk source code from a buffer and returns a tokenized output together
+/// with its syntax tree.
 ///
 /// # Arguments
 /// - `input_file_reader`: Buffer with runk source code
@@ -128,11 +375,13 @@ pub enum ParseResult<'a> {
 /// - `Ok`: A vector of (tokenized) Lines.
 /// - `Err`:
 ///     - String: Description of the issue intended to be shown to the user.
-///     - usize: Length of the nesting stack. TODO Replace with a line number.
-pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
-                      file_name: &'a str,
-                      line_number: usize,
-                      prompt: bool) -> ParseResult<'a> {
+///     - usize: Line number relative to the first line that was read.
+///     - String: Source of the line.
+///     - Option<Word>: The problematic word.
+pub fn parse_file(input_file_reader: &mut dyn BufRead,
+                  file_name: &str,
+                  line_number: usize,
+                  prompt: bool) -> ParseResult {
     // This variable will eventually be returned.
     // Line of the current interation.
     let mut line             = line::Line::new(&file_name, 1, false);
@@ -144,6 +393,10 @@ pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
     let mut acc_literally    = false;
     // Line number relative to the beggining of this effective line.
     let mut curr_line_number = 0;
+    // Every line read so far, used for reporting errors.
+    let mut input_lines      = Vec::<String>::new();
+    // Index in `input_lines` and column of the last opening quote.
+    let mut literal_start    = (0, 0);
 
 
     loop {
@@ -168,24 +421,66 @@ pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
                         return ParseResult::Err(
                             format!("Nesting error, missing a closing \"{}\"!",
                                     &nesting_stack[nesting_stack.len()-1].string.italic()),
-                            nesting_stack.len()+1
+                            input_lines.len(),
+                            input_lines.last().unwrap().trim_end().to_string(),
+                            None,
                         );
                     }
                     // Line will should always be worthless (I think).
                     return ParseResult::Eof;
                 }
             },
-            Err(e) => return ParseResult::Err(e.to_string(), line_number+curr_line_number),
+            Err(e) => return ParseResult::Err(e.to_string(), curr_line_number, String::new(), None),
         };
+        input_lines.push(curr_input_line.clone());
 
         // Recording the column where the current word started for runk debug.
         let mut word_start_column = 0;
 
         // A handy macro for creating words and pushing them.
         macro_rules! push_rtoken {
-            ( $acc:ident, $line_number:ident, $i_char:ident ) => {
+            ( $acc:ident, $line_number:ident ) => {
                 if $acc.len() > 0 {
-                    match parse_string(&$acc[..]) {
+                    let parsed = if $acc.len() >= 2 && $acc.starts_with('"') && $acc.ends_with('"') {
+                        match parse_text_literal(&$acc[1..$acc.len()-1], literal_start.1, $line_number) {
+                            Ok(rt) => Some((rt, None)),
+                            Err((e, offset, length)) => {
+                                // Finding the problematic part of the literal in the source.
+                                let chars: Vec<char> = $acc[1..].chars().collect();
+                                let newlines: Vec<usize> = chars[..offset].iter().enumerate()
+                                    .filter(|(_, c)| **c == '\n').map(|(i, _)| i).collect();
+                                let index = literal_start.0 + newlines.len();
+                                let column = match newlines.last() {
+                                    Some(newline) => offset - newline - 1,
+                                    None => literal_start.1 + 1 + offset,
+                                };
+                                let word = word::Word {
+                                    rtoken: rtoken::Rtoken::Plain(chars[offset..offset+length].iter().collect()),
+                                    original: chars[offset..offset+length].iter().collect(),
+                                    column,
+                                    line: $line_number,
+                                    parsed_line: line_number,
+                                };
+                                return ParseResult::Err(e, index+1, input_lines[index].trim_end().to_string(), Some(word));
+                            },
+                        }
+                    }
+                    // Malformed numbers are reported right away, so the problematic digit can be shown.
+                    else if let Some(Err((e, offset, length))) = var::Var::num_literal(&$acc) {
+                        let chars: Vec<char> = $acc.chars().collect();
+                        let word = word::Word {
+                            rtoken: rtoken::Rtoken::Plain(chars[offset..offset+length].iter().collect()),
+                            original: chars[offset..offset+length].iter().collect(),
+                            column: word_start_column + offset,
+                            line: $line_number,
+                            parsed_line: line_number,
+                        };
+                        return ParseResult::Err(e, input_lines.len(), input_lines.last().unwrap().trim_end().to_string(), Some(word));
+                    }
+                    else {
+                        parse_string(&$acc[..])
+                    };
+                    match parsed {
                         Some((rt, cancel)) => {
                             // Pusing word to line
                             line.content.push(
@@ -198,7 +493,6 @@ pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
                                 });
                             // Reseting acc
                             $acc = String::new();
-                            word_start_column = $i_char+1;
                             // Push canceling char to nesting stack.
                             match cancel {
                                 None => {},
@@ -215,9 +509,8 @@ pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
 
 
         // For every char in every line
-        for i_char in 0..curr_input_line.chars().count() {
-            // Current char.
-            let c = curr_input_line.chars().nth(i_char).unwrap();
+        // Single pass over chars of the line, `i_char` is the column of `c`.
+        for (i_char, c) in curr_input_line.chars().enumerate() {
 
             if skip_next_c == true {
                 if acc_literally {
@@ -228,23 +521,31 @@ pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
                 continue;
             }
 
+            // Escape sequences in literals are kept as they are and decoded with the whole literal.
+            if acc_literally && c == '\\' {
+                accumulator.push(c);
+                skip_next_c = true;
+                continue;
+            }
+
             // Popping nesting of pair operators like () or ""
             if nesting_stack.len() > 0 {
-                if nesting_stack.iter().last().unwrap().string == c.to_string() {
-                    if let PushString::Yes = nesting_stack.iter().last().unwrap().push_string {
+                if nesting_stack.last().unwrap().string == c.to_string() {
+                    if let PushString::Yes = nesting_stack.last().unwrap().push_string {
                         accumulator.push(c);
-                        push_rtoken!(accumulator, line_number, i_char);
+                        push_rtoken!(accumulator, line_number);
                     }
-                    else if let PushString::Separately = nesting_stack.iter().last().unwrap().push_string  {
-                        push_rtoken!(accumulator, line_number, i_char);
+                    else if let PushString::Separately = nesting_stack.last().unwrap().push_string  {
+                        push_rtoken!(accumulator, line_number);
+                        word_start_column = i_char;
                         let mut string = c.to_string();
-                        push_rtoken!(string, line_number, i_char);
+                        push_rtoken!(string, line_number);
 
                     }
                     nesting_stack.pop();
 
                     if nesting_stack.len() != 0 {
-                        acc_literally = nesting_stack.iter().last().unwrap().acc_literally;
+                        acc_literally = nesting_stack.last().unwrap().acc_literally;
                     }
                     else {
                         acc_literally = false;
@@ -267,20 +568,38 @@ pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
             if c == '#' {
                 break;
             }
+            // Closing bracket that doesn't close the innermost open one.
+            else if matches!(c, ')' | ']' | '}') {
+                let message = match nesting_stack.last() {
+                    Some(open) => format!("Nesting error, unexpected \"{}\" before a closing \"{}\"!", c, open.string.italic()),
+                    None => format!("Nesting error, unexpected \"{}\" without an opening bracket!", c),
+                };
+                let word = word::Word {
+                    rtoken: rtoken::Rtoken::Plain(c.to_string()),
+                    original: c.to_string(),
+                    column: i_char,
+                    line: line_number,
+                    parsed_line: line_number,
+                };
+                return ParseResult::Err(message, input_lines.len(), input_lines.last().unwrap().trim_end().to_string(), Some(word));
+            }
             // Push acc, discard c.
             else if c.is_whitespace() {
-                push_rtoken!(accumulator, line_number, i_char);
+                push_rtoken!(accumulator, line_number);
             }
             // Push acc & then separately c.
             else if is_special_operator(&c) {
-                push_rtoken!(accumulator, line_number, i_char);
+                push_rtoken!(accumulator, line_number);
+                word_start_column = i_char;
                 let mut string = c.to_string();
-                push_rtoken!(string, line_number, i_char);
+                push_rtoken!(string, line_number);
             }
             // Text literals
             else if c == '"' {
-                push_rtoken!(accumulator, line_number, i_char);
+                push_rtoken!(accumulator, line_number);
                 acc_literally = true;
+                literal_start = (input_lines.len()-1, i_char);
+                word_start_column = i_char;
                 nesting_stack.push(OpCancel {
                     string: "\"".to_string(),
                     push_string: PushString::Yes,
@@ -292,28 +611,272 @@ pub fn parse_file<'a>(input_file_reader: &mut Box<dyn BufRead>,
             }
             // c to acc.
             else {
+                if accumulator.is_empty() {
+                    word_start_column = i_char;
+                }
                 let string = c.to_string();
                 accumulator.push_str(&string[..]);
             }
         }
 
-        if acc_literally {
-            // Add the endline to acc because .lines() removes them
-            accumulator.push_str("\n");
-        }
-        else {
+        // The endline was already added to acc if it's a literal.
+        if !acc_literally {
             // End of line is also a space, so we need to push acc.
-            let zero_for_my_beautifull_macro = 0;
-            push_rtoken!(accumulator, line_number, zero_for_my_beautifull_macro);
+            push_rtoken!(accumulator, line_number);
         }
 
         if nesting_stack.len() == 0 {
-            line.original = curr_input_line[..curr_input_line.len()-1].to_string();
+            line.original = Rc::from(&curr_input_line[..curr_input_line.len()-1]);
             line.line_number = line_number + curr_line_number;
             if line.content.len() == 0 {
                 continue;
             }
-            return ParseResult::Ok(line, curr_line_number);
+            // A lone ">" closes a block, anywhere else it's just the function name.
+            if line.content.len() == 1 && line.content[0].rtoken == rtoken::Rtoken::Plain(">".to_string()) {
+                line.content[0].rtoken = rtoken::Rtoken::BlockEnd;
+            }
+            line.statement = match ast::Statement::new(&line.content) {
+                Ok(statement) => statement,
+                Err((e, opt_word)) => return ParseResult::Err(e, curr_line_number, line.original.to_string(), opt_word),
+            };
+            return ParseResult::Ok(Box::new(line), curr_line_number);
         }
     }
 }
+
+
+#[cfg(test)]
+fn tokens(source: &str) -> Vec<rtoken::Rtoken> {
+    match parse_file(&mut io::Cursor::new(source), "", 0, false) {
+        ParseResult::Ok(line, _) => line.content.into_iter().map(|w| w.rtoken).collect(),
+        ParseResult::Err(e, _, _, _) => panic!("{}", e),
+        ParseResult::Eof => Vec::new(),
+    }
+}
+
+#[test]
+fn test_every_token() {
+    use rtoken::Rtoken::*;
+    let text = |s: &str| s.to_string();
+    let data_type = |var: var::Var| DataType(Box::new(var));
+
+    // Source of a line and the token of its word at the given index.
+    let table: Vec<(&str, usize, rtoken::Rtoken)> = vec!(
+        ("Nat x: 1\n",                2, Assign),
+        ("Nat x: 1\n",                0, data_type(var::Var::n(Zero::zero()).unwrap())),
+        ("Int x: 1\n",                0, data_type(var::Var::z(Zero::zero()).unwrap())),
+        ("Rat x: 1\n",                0, data_type(var::Var::q(Zero::zero(), One::one()).unwrap())),
+        ("Dec x: 1\n",                0, data_type(var::Var::d(Zero::zero(), 0).unwrap())),
+        ("Txt x: 1\n",                0, data_type(var::Var::t(String::new()).unwrap())),
+        ("Lab x: 1\n",                0, data_type(var::Var::l(String::new()).unwrap())),
+        ("Lst x: 1\n",                0, data_type(var::Var::a(Vec::new()).unwrap())),
+        ("Map x: 1\n",                0, data_type(var::Var::m(Default::default()).unwrap())),
+        ("(f)\n",                     0, FunctionStart),
+        ("(f)\n",                     2, FunctionEnd),
+        ("(f) else (g)\n",            3, OnFunctionFail),
+        ("[1 + 2]\n",                 0, InfixStart),
+        ("[1 + 2]\n",                 4, InfixEnd),
+        ("{1 2}\n",                   0, ListStart),
+        ("{1 2}\n",                   3, ListEnd),
+        ("{\"a\": 1}\n",              2, Assign),
+        ("{:}\n",                     1, Assign),
+        ("<while 1\n",                0, BlockStart(text("while"))),
+        ("><elif 1\n",                0, BlockChain(text("elif"))),
+        (">\n",                       0, BlockEnd),
+        ("ret 1\n",                   0, Return),
+        ("break\n",                   0, Break),
+        ("continue\n",                0, Continue),
+        ("const Nat x: 1\n",          0, Const),
+        ("\"a b\"\n",                 0, TextLiteral(text("a b"))),
+        ("!end\n",                    0, LableLiteral(text("end"))),
+        ("1\n",                       0, NumLiteral(text("1"))),
+        ("-0xfF\n",                   0, NumLiteral(text("-0xfF"))),
+        ("0o17\n",                    0, NumLiteral(text("0o17"))),
+        ("0b1_0\n",                   0, NumLiteral(text("0b1_0"))),
+        ("1_000.5\n",                 0, NumLiteral(text("1_000.5"))),
+        ("+3/4\n",                    0, NumLiteral(text("+3/4"))),
+        ("$x_1\n",                    0, VariableReference(text("x_1"))),
+        ("(> 2 1)\n",                 1, Plain(text(">"))),
+        ("(x)\n",                     1, Plain(text("x"))),
+    );
+    for (source, index, rtoken) in table {
+        assert_eq!(tokens(source)[index], rtoken, "{:?}", source);
+    }
+    assert!(matches!(&tokens("\"a$x\"\n")[0], InterpolatedText(parts) if parts.len() == 2));
+}
+
+#[test]
+fn test_expression_tokens() {
+    use rtoken::Rtoken::*;
+    let plain = |s: &str| Plain(s.to_string());
+
+    let rtokens = tokens("Nat x: (f [1 + $y] \"a\\n\" !l) else (g)\n");
+    assert_eq!(rtokens, vec!(
+        DataType(Box::new(var::Var::n(Zero::zero()).unwrap())), plain("x"), Assign,
+        FunctionStart, plain("f"),
+        InfixStart, NumLiteral("1".to_string()), plain("+"), VariableReference("y".to_string()), InfixEnd,
+        TextLiteral("a\n".to_string()), LableLiteral("l".to_string()), FunctionEnd,
+        OnFunctionFail, FunctionStart, plain("g"), FunctionEnd,
+    ));
+
+    let rtokens = tokens("Txt t: \"a$y $(+ 1 2)\"\n");
+    assert_eq!(rtokens[0], DataType(Box::new(var::Var::t(String::new()).unwrap())));
+    match &rtokens[3] {
+        InterpolatedText(parts) => {
+            assert_eq!(parts.len(), 4);
+            assert_eq!(parts[0], rtoken::TextPart::Text("a".to_string()));
+            assert!(matches!(&parts[1], rtoken::TextPart::Expression(words) if words[0].rtoken == VariableReference("y".to_string())));
+            assert_eq!(parts[2], rtoken::TextPart::Text(" ".to_string()));
+            assert!(matches!(&parts[3], rtoken::TextPart::Expression(words) if words.len() == 5 && words[4].rtoken == FunctionEnd));
+        },
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_interpolation() {
+    use rtoken::TextPart;
+    let parts = |source: &str| match tokens(source).pop() {
+        Some(rtoken::Rtoken::InterpolatedText(parts)) => parts,
+        other => panic!("{:?}", other),
+    };
+    // Columns of the embedded words point into the literal.
+    let columns = |part: &TextPart| match part {
+        TextPart::Expression(words) => words.iter().map(|w| w.column).collect::<Vec<usize>>(),
+        TextPart::Text(_) => Vec::new(),
+    };
+
+    let interpolated = parts("\"$a_1, $(f $b) $[1 + 2]!\"\n");
+    assert_eq!(interpolated.len(), 6);
+    assert_eq!(columns(&interpolated[0]), vec!(1));
+    assert_eq!(interpolated[1], TextPart::Text(", ".to_string()));
+    assert_eq!(columns(&interpolated[2]), vec!(8, 9, 11, 13));
+    assert_eq!(columns(&interpolated[4]), vec!(16, 17, 19, 21, 22));
+    assert_eq!(interpolated[5], TextPart::Text("!".to_string()));
+    assert_eq!(columns(&parts("\"a\n  b$c\"\n")[1]), vec!(3));
+    // Dollar signs not followed by a name stay in the text.
+    assert_eq!(tokens("\"$ 5$\"\n"), vec!(rtoken::Rtoken::TextLiteral("$ 5$".to_string())));
+
+    match parse_file(&mut io::Cursor::new("Txt t: \"a $(+ 1\"\n"), "", 0, false) {
+        ParseResult::Err(e, _, _, Some(word)) => {
+            assert!(e.contains("missing a closing bracket"));
+            assert_eq!(word.column, 10);
+        },
+        _ => panic!("Unclosed expression was accepted."),
+    }
+    assert!(matches!(parse_file(&mut io::Cursor::new("\"$()\"\n"), "", 0, false), ParseResult::Err(..)));
+}
+
+#[test]
+fn test_interpolation_values() {
+    let mut interpreter = crate::Interpreter::new();
+    interpreter.run_str("Nat n: 4\nTxt t: \"$n * 2 = $[$n * 2] and $(+ $n 1)\"\n").unwrap();
+    assert_eq!(interpreter.get_var("t").unwrap().plain_string(), "4 * 2 = 8 and 5");
+
+    let error = interpreter.run_str("Txt u: \"a\n  b$missing\"\n").unwrap_err();
+    assert_eq!((error.line_number, error.column), (2, Some(3)));
+    let error = interpreter.run_str("Txt u: \"$(+ 1 $missing)\"\n").unwrap_err();
+    assert_eq!((error.line_number, error.column), (1, Some(14)));
+}
+
+#[test]
+fn test_escape_sequences() {
+    use rtoken::Rtoken::TextLiteral;
+    assert_eq!(tokens("\"a\\nb\\t\\r\\0\\\\\\\"\\$x\"\n"), vec!(TextLiteral("a\nb\t\r\0\\\"$x".to_string())));
+    assert_eq!(tokens("\"\\u{41}\\u{1F600}\"\n"), vec!(TextLiteral("A\u{1F600}".to_string())));
+    // Backslash at the end of a line joins it with the next one.
+    assert_eq!(tokens("\"ab\\\ncd\"\n"), vec!(TextLiteral("abcd".to_string())));
+
+    // Error message, line and column of the invalid sequence.
+    let error = |source: &str| match parse_file(&mut io::Cursor::new(source), "", 0, false) {
+        ParseResult::Err(e, line, _, Some(word)) => (e, line, word.column, word.original),
+        _ => panic!("\"{}\" was accepted.", source),
+    };
+    let (e, line, column, original) = error("Txt a: \"x\\q\"\n");
+    assert!(e.contains("Unknown escape sequence"));
+    assert_eq!((line, column, &original[..]), (1, 9, "\\q"));
+    let (e, line, column, original) = error("Txt a: \"ab\\u{110000}\"\n");
+    assert!(e.contains("not a valid unicode character"));
+    assert_eq!((line, column, &original[..]), (1, 10, "\\u{110000}"));
+    let (e, _, column, _) = error("Txt a: \"\\u41\"\n");
+    assert!(e.contains("has to look like"));
+    assert_eq!(column, 8);
+    let (_, line, column, _) = error("Txt a: \"a\n  b\\q\"\n");
+    assert_eq!((line, column), (2, 3));
+}
+
+#[test]
+fn test_keyword_tokens() {
+    use rtoken::Rtoken::*;
+
+    assert_eq!(tokens("<fn Int f Lab l\n"), vec!(BlockStart("fn".to_string()),
+                                                DataType(Box::new(var::Var::z(Zero::zero()).unwrap())), Plain("f".to_string()),
+                                                DataType(Box::new(var::Var::l(String::new()).unwrap())), Plain("l".to_string())));
+    assert_eq!(tokens("><else\n"), vec!(BlockChain("else".to_string())));
+    assert_eq!(tokens(">\n"), vec!(BlockEnd));
+    assert_eq!(tokens("(> 2 1)\n")[1], Plain(">".to_string()));
+    assert_eq!(tokens("ret 1\n"), vec!(Return, NumLiteral("1".to_string())));
+    assert_eq!(tokens("ret -3/4\n"), vec!(Return, NumLiteral("-3/4".to_string())));
+    assert_eq!(tokens("break\n"), vec!(Break));
+    assert_eq!(tokens("continue\n"), vec!(Continue));
+    assert_eq!(tokens("!start # comment\n"), vec!(LableLiteral("start".to_string())));
+    assert_eq!(tokens("\n\n# comment\n"), Vec::new());
+}
+
+#[test]
+fn test_list_tokens() {
+    use rtoken::Rtoken::*;
+
+    assert_eq!(tokens("Lst l: {1 {\"a\"}}\n"), vec!(DataType(Box::new(var::Var::a(Vec::new()).unwrap())), Plain("l".to_string()), Assign,
+                                                  ListStart, NumLiteral("1".to_string()),
+                                                  ListStart, TextLiteral("a".to_string()), ListEnd, ListEnd));
+    // Lists can span multiple lines.
+    assert_eq!(tokens("{1\n 2}\n").len(), 4);
+}
+
+#[test]
+fn test_number_tokens() {
+    use rtoken::Rtoken::*;
+    assert_eq!(tokens("ret {0xFF 1_000 -0b10}\n"), vec!(Return, ListStart, NumLiteral("0xFF".to_string()),
+                                                      NumLiteral("1_000".to_string()), NumLiteral("-0b10".to_string()), ListEnd));
+
+    // Malformed numbers point at the problematic digit.
+    match parse_file(&mut io::Cursor::new("Nat a: 0b102\n"), "", 0, false) {
+        ParseResult::Err(e, line, _, Some(word)) => {
+            assert!(e.contains("binary"));
+            assert_eq!((line, word.column, &word.original[..]), (1, 11, "2"));
+        },
+        _ => panic!("Malformed number was accepted."),
+    }
+}
+
+#[test]
+fn test_multi_line_nesting() {
+    let mut reader = io::Cursor::new("(f (g\n   1)\n  \"a\nb\") # c\nNat x: 2\n");
+
+    let line = match parse_file(&mut reader, "", 0, false) {
+        ParseResult::Ok(line, relative_line_number) => {
+            assert_eq!(relative_line_number, 4);
+            line
+        },
+        _ => panic!(),
+    };
+    let originals: Vec<&str> = line.content.iter().map(|w| &w.original[..]).collect();
+    assert_eq!(originals, vec!("(", "f", "(", "g", "1", ")", "\"a\nb\"", ")"));
+    assert_eq!(line.content[6].rtoken, rtoken::Rtoken::TextLiteral("a\nb".to_string()));
+    assert_eq!(&line.original[..], "b\") # c");
+
+    match parse_file(&mut reader, "", 4, false) {
+        ParseResult::Ok(line, _) => assert_eq!(line.line_number, 5),
+        _ => panic!(),
+    }
+
+    match parse_file(&mut io::Cursor::new("(f (g\n  1\n"), "", 0, false) {
+        ParseResult::Err(e, relative_line_number, original, _) => {
+            assert!(e.starts_with("Nesting error"));
+            assert_eq!(relative_line_number, 2);
+            assert_eq!(original, "  1");
+        },
+        _ => panic!(),
+    }
+}
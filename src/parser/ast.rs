@@ -0,0 +1,709 @@
+//!
+//! Syntax tree of runk code. Every line is turned into a `Statement` once when it's
+//! parsed, the interpreter then only walks the tree.
+//!
+
+use colored::Colorize;
+use num_traits::Zero;
+use crate::structs::{ var, word, assign };
+use super::rtoken;
+
+/// Description of an issue intended to be shown to the user and the problematic word.
+pub type SyntaxError = (String, Option<word::Word>);
+
+/// What a single line of runk code does.
+#[derive(Debug, Clone)]
+pub enum Statement {
+    /// Line without any code.
+    Empty,
+    /// Value of the expression is assigned to a variable or printed if there is no assignment.
+    Expression(Option<assign::Assign>, Expression),
+    /// Leaves a function with the value of the expression.
+    Return(Expression),
+    /// Declaration of a lable.
+    Lable(String),
+    Break,
+    Continue,
+    /// Line opening a block like "<if" with the condition that follows it. A "<for" loop has
+    /// its list of items instead. Functions have no condition, their header and the name of
+    /// the loop variable are read from the words of the line.
+    BlockStart(String, Option<Expression>),
+    /// Line starting another part of a block like "><elif" with its condition.
+    BlockChain(String, Option<Expression>),
+    /// Lone ">" closing a block.
+    BlockEnd,
+}
+
+/// Part of a statement that results in a value.
+#[derive(Debug, Clone)]
+pub enum Expression {
+    /// Value written directly in the code.
+    Literal(var::Var, word::Word),
+    /// Value of a variable like "$x".
+    Variable(String, word::Word),
+    /// Function call, operators of infix expressions are calls as well.
+    Call(Call),
+    /// Text literal with embedded expressions.
+    Text(Vec<TextPart>),
+    /// List literal like "{1 2 $x}", its items are resolved every time it's used.
+    List(Vec<Expression>),
+    /// Map literal like "{"a": 1 $key: 2}" with pairs of keys and values and its opening bracket.
+    Map(Vec<(Expression, Expression)>, word::Word),
+}
+
+/// Call of a runk function.
+#[derive(Debug, Clone)]
+pub struct Call {
+    /// Word with the name of the function.
+    pub function: word::Word,
+    pub args: Vec<Expression>,
+    /// What happens when the function or one of its arguments fails.
+    pub on_fail: Option<Box<OnFail>>,
+}
+
+/// Alternative following "else" after a function call.
+#[derive(Debug, Clone)]
+pub enum OnFail {
+    /// Replacement function whose value is used instead.
+    Call(Call),
+    /// Lable to jump to without finishing the statement.
+    Jump(String, word::Word),
+}
+
+/// Part of an interpolated text literal.
+#[derive(Debug, Clone)]
+pub enum TextPart {
+    Text(String),
+    Expression(Expression),
+}
+
+impl Statement {
+    /// # Description
+    /// Creates a statement from the words of a line.
+    ///
+    /// # Returns
+    /// - `Ok`: The statement.
+    /// - `Err`: The words don't form a valid statement.
+    pub fn new(words: &[word::Word]) -> Result<Statement, SyntaxError> {
+        if words.is_empty() {
+            return Ok(Statement::Empty);
+        }
+
+        match &words[0].rtoken {
+            rtoken::Rtoken::LableLiteral(lable) if words.len() == 1 => Ok(Statement::Lable(lable.clone())),
+            rtoken::Rtoken::BlockEnd => Ok(Statement::BlockEnd),
+            rtoken::Rtoken::Break | rtoken::Rtoken::Continue => {
+                expect_end(words, 1, &words[0])?;
+                match words[0].rtoken {
+                    rtoken::Rtoken::Break => Ok(Statement::Break),
+                    _ => Ok(Statement::Continue),
+                }
+            },
+            rtoken::Rtoken::Return => {
+                let (expression, end) = parse_expression(&words[1..], &words[0])?;
+                expect_end(words, 1+end, &words[0])?;
+                Ok(Statement::Return(expression))
+            },
+            rtoken::Rtoken::BlockStart(kind) => {
+                match &kind[..] {
+                    "fn" => Ok(Statement::BlockStart(kind.clone(), None)),
+                    "if" | "while" => Ok(Statement::BlockStart(kind.clone(), Some(parse_condition(words)?))),
+                    "for" => Ok(Statement::BlockStart(kind.clone(), Some(parse_items(words)?))),
+                    _ => Err((format!("Unknown block \"{}\"!", words[0].original.italic()), Some(words[0].clone()))),
+                }
+            },
+            rtoken::Rtoken::BlockChain(kind) => {
+                match &kind[..] {
+                    "else" => {
+                        if words.len() > 1 {
+                            return Err((format!("Unexpected token \"{}\" after \"{}\"!", words[1].original, "><else".italic()),
+                                        Some(words[1].clone())));
+                        }
+                        Ok(Statement::BlockChain(kind.clone(), None))
+                    },
+                    "elif" => Ok(Statement::BlockChain(kind.clone(), Some(parse_condition(words)?))),
+                    _ => Err((format!("Unknown block part \"{}\"!", words[0].original.italic()), Some(words[0].clone()))),
+                }
+            },
+            _ => {
+                let (assign, start) = parse_assignment(words)?;
+                let (expression, end) = parse_expression(&words[start..], &words[start.saturating_sub(1)])?;
+                if start + end < words.len() {
+                    return Err((format!("Unexpected token \"{}\" after expression!", &words[start+end].original),
+                                Some(words[start+end].clone())));
+                }
+                Ok(Statement::Expression(assign, expression))
+            },
+        }
+    }
+}
+
+/// Makes sure there are no words after `end`.
+fn expect_end(words: &[word::Word], end: usize, keyword: &word::Word) -> Result<(), SyntaxError> {
+    if end < words.len() {
+        return Err((format!("Unexpected token \"{}\" after \"{}\"!", words[end].original, keyword.original.italic()),
+                    Some(words[end].clone())));
+    }
+
+    Ok(())
+}
+
+/// Parses the condition following the first word of a block header like "<if".
+fn parse_condition(words: &[word::Word]) -> Result<Expression, SyntaxError> {
+    if words.len() < 2 {
+        return Err(("Missing condition!".to_string(), Some(words[0].clone())));
+    }
+
+    let (expression, end) = parse_expression(&words[1..], &words[0])?;
+    if 1 + end < words.len() {
+        return Err((format!("Unexpected token \"{}\" after condition!", &words[1+end].original),
+                    Some(words[1+end].clone())));
+    }
+
+    Ok(expression)
+}
+
+/// Parses the name of the loop variable and the list of items following "<for".
+fn parse_items(words: &[word::Word]) -> Result<Expression, SyntaxError> {
+    match words.get(1).map(|w| &w.rtoken) {
+        Some(rtoken::Rtoken::Plain(_)) => {},
+        Some(_) => return Err((format!("Expected a variable name after \"{}\", found \"{}\"!", words[0].original.italic(), words[1].original),
+                               Some(words[1].clone()))),
+        None => return Err(("Missing variable name!".to_string(), Some(words[0].clone()))),
+    }
+    if words.len() < 3 {
+        return Err(("Missing list of items!".to_string(), Some(words[1].clone())));
+    }
+
+    let (expression, end) = parse_expression(&words[2..], &words[1])?;
+    expect_end(words, 2 + end, &words[0])?;
+
+    Ok(expression)
+}
+
+/// # Description
+/// Finds an assignment at the start of a line.
+///
+/// # Returns
+/// - `Ok`:
+///     - `Option<Assign>`: Is None if the `words` didn't contain an assignment.
+///     - `usize`: Index of the first word of the expression.
+/// - `Err`: The assignment is not valid.
+fn parse_assignment(words: &[word::Word]) -> Result<(Option<assign::Assign>, usize), SyntaxError> {
+    // Constants are declarations with "const" in front of them.
+    if words.first().is_some_and(|w| w.rtoken == rtoken::Rtoken::Const) {
+        return match parse_assignment(&words[1..])? {
+            (Some(assign::Assign::Dec(dtype, name)), start) => Ok((Some(assign::Assign::Const(dtype, name)), start + 1)),
+            _ => Err((format!("\"{}\" has to be followed by a declaration with a type!", words[0].original.italic()), Some(words[0].clone()))),
+        };
+    }
+
+    // Colons inside of brackets belong to map literals.
+    let mut depth = 0;
+    let exp_start_index = match words.iter().position(|w| {
+        match w.rtoken {
+            rtoken::Rtoken::FunctionStart | rtoken::Rtoken::InfixStart | rtoken::Rtoken::ListStart => depth += 1,
+            rtoken::Rtoken::FunctionEnd | rtoken::Rtoken::InfixEnd | rtoken::Rtoken::ListEnd => depth -= 1,
+            _ => {},
+        }
+        depth == 0 && w.rtoken == rtoken::Rtoken::Assign
+    }) {
+        Some(i) => i + 1,
+        None => return Ok((None, 0)),
+    };
+
+    // Check if expression is missing.
+    if words.len() <= exp_start_index {
+        return Err(("Missing expression!".to_string(), Some(words[words.len()-1].clone())));
+    }
+
+    match exp_start_index {
+        // Nondeclarative
+        2 => {
+            if let rtoken::Rtoken::Plain(name) = &words[0].rtoken {
+                return Ok((Some(assign::Assign::Nondec(name.to_string())), exp_start_index));
+            }
+            Err((format!("Variable name \"{}\" is invalid!", words[0].original.italic()), Some(words[0].clone())))
+        },
+        // Declarative
+        3 => {
+            if let rtoken::Rtoken::DataType(dtype) = &words[0].rtoken {
+                if let rtoken::Rtoken::Plain(name) = &words[1].rtoken {
+                    return Ok((Some(assign::Assign::Dec((**dtype).clone(), name.to_string())), exp_start_index));
+                }
+                return Err((format!("Variable name \"{}\" is invalid!", words[1].original.italic()), Some(words[1].clone())));
+            }
+            Err((format!("Variable type \"{}\" is invalid!", words[0].original.italic()), Some(words[0].clone())))
+        },
+        _ => Err((format!("Unexpected token \"{}\"!", words[exp_start_index-1].original.italic()), Some(words[exp_start_index-1].clone()))),
+    }
+}
+
+/// # Description
+/// Parses a single expression from the begining of `input`.
+///
+/// # Args
+/// - `input`: Words starting with the expression, no cropping from the right though.
+/// - `previous`: Word right before `input` used for reporting a missing expression.
+///
+/// # Returns
+/// - `Ok`:
+///     - `Expression`: The expression.
+///     - `usize`: Amount of words taken by the expression.
+/// - `Err`: The words don't form a valid expression.
+pub fn parse_expression(input: &[word::Word], previous: &word::Word) -> Result<(Expression, usize), SyntaxError> {
+    if input.is_empty() {
+        return Err(("Missing expression!".to_string(), Some(previous.clone())));
+    }
+
+    let literal = |result: Result<var::Var, String>| -> Result<(Expression, usize), SyntaxError> {
+        match result {
+            Ok(v) => Ok((Expression::Literal(v, input[0].clone()), 1)),
+            Err(e) => Err((e, Some(input[0].clone()))),
+        }
+    };
+
+    match &input[0].rtoken {
+        rtoken::Rtoken::FunctionStart => {
+            let (call, end) = parse_call(input)?;
+            Ok((Expression::Call(call), end))
+        },
+        rtoken::Rtoken::InfixStart => {
+            let (expression, end) = parse_infix_expression(&input[1..], &input[0])?;
+            Ok((expression, end+1))
+        },
+        rtoken::Rtoken::VariableReference(name) => Ok((Expression::Variable(name.clone(), input[0].clone()), 1)),
+        rtoken::Rtoken::TextLiteral(_) => literal(var::Var::text_from_word(&input[0])),
+        rtoken::Rtoken::LableLiteral(_) => literal(var::Var::lable_from_word(&input[0])),
+        rtoken::Rtoken::NumLiteral(_) => literal(var::Var::num_from_word(&input[0])),
+        rtoken::Rtoken::InterpolatedText(parts) => Ok((Expression::Text(parse_text_parts(parts)?), 1)),
+        rtoken::Rtoken::ListStart => parse_list(input),
+        _ => Err((format!("Invalid token \"{}\" in a function.", input[0].original.italic()), Some(input[0].clone()))),
+    }
+}
+
+/// Parses expressions embedded in an interpolated text literal.
+fn parse_text_parts(parts: &[rtoken::TextPart]) -> Result<Vec<TextPart>, SyntaxError> {
+    let mut result = Vec::with_capacity(parts.len());
+    for part in parts {
+        match part {
+            rtoken::TextPart::Text(text) => result.push(TextPart::Text(text.clone())),
+            rtoken::TextPart::Expression(words) => {
+                let (expression, end) = parse_expression(words, &words[0])?;
+                if end < words.len() {
+                    return Err((format!("Unexpected token \"{}\" after expression!", words[end].original),
+                                Some(words[end].clone())));
+                }
+                result.push(TextPart::Expression(expression));
+            },
+        }
+    }
+
+    Ok(result)
+}
+
+/// # Description
+/// Parses a list literal or a map literal. Items of a map are separated from their keys by
+/// colons, an empty map is written as "{:}".
+///
+/// # Args
+/// - `input`: Words starting with the opening bracket of the literal.
+///
+/// # Returns
+/// - `Ok`:
+///     - `Expression`: The list or the map.
+///     - `usize`: Amount of words taken by the literal including the closing bracket.
+/// - `Err`: The words don't form a valid literal.
+fn parse_list(input: &[word::Word]) -> Result<(Expression, usize), SyntaxError> {
+    let is_map = match input.get(1) {
+        Some(w) if w.rtoken == rtoken::Rtoken::Assign => {
+            return match input.get(2) {
+                Some(w) if w.rtoken == rtoken::Rtoken::ListEnd => Ok((Expression::Map(Vec::new(), input[0].clone()), 3)),
+                _ => Err((format!("Empty map has to be written as \"{}\"!", "{:}".italic()), Some(w.clone()))),
+            };
+        },
+        Some(w) if w.rtoken != rtoken::Rtoken::ListEnd => {
+            let (_, end) = parse_expression(&input[1..], &input[0])?;
+            input.get(1+end).is_some_and(|w| w.rtoken == rtoken::Rtoken::Assign)
+        },
+        _ => false,
+    };
+
+    let mut items = Vec::new();
+    let mut pairs = Vec::new();
+    let mut i = 1;
+    loop {
+        match input.get(i) {
+            None => return Err(("List ends abruptly!".to_string(), Some(input[0].clone()))),
+            Some(w) if w.rtoken == rtoken::Rtoken::ListEnd => break,
+            Some(_) => {
+                let (item, end) = parse_expression(&input[i..], &input[i-1])?;
+                i += end;
+                if !is_map {
+                    items.push(item);
+                    continue;
+                }
+
+                match input.get(i) {
+                    Some(w) if w.rtoken == rtoken::Rtoken::Assign => {},
+                    Some(w) => return Err((format!("Expected \"{}\" after a key of a map but found \"{}\"!", ":".italic(), w.original.italic()),
+                                           Some(w.clone()))),
+                    None => return Err(("List ends abruptly!".to_string(), Some(input[0].clone()))),
+                }
+                let (value, end) = parse_expression(&input[i+1..], &input[i])?;
+                pairs.push((item, value));
+                i += 1 + end;
+            },
+        }
+    }
+
+    match is_map {
+        true => Ok((Expression::Map(pairs, input[0].clone()), i+1)),
+        false => Ok((Expression::List(items), i+1)),
+    }
+}
+
+/// # Description
+/// Parses a function call together with the alternatives following it.
+///
+/// # Args
+/// - `input`: Words starting with the opening bracket of the call.
+///
+/// # Returns
+/// - `Ok`:
+///     - `Call`: The call.
+///     - `usize`: Amount of words taken by the call.
+/// - `Err`: The words don't form a valid call.
+fn parse_call(input: &[word::Word]) -> Result<(Call, usize), SyntaxError> {
+    let function = match input.get(1) {
+        None => return Err(("Expression ends abruptly!".to_string(), Some(input[0].clone()))),
+        Some(w) if w.rtoken == rtoken::Rtoken::FunctionEnd => return Err(("Function name is missing".to_string(), Some(w.clone()))),
+        Some(w) => match &w.rtoken {
+            rtoken::Rtoken::Plain(_) => w.clone(),
+            _ => return Err((format!("Invalid token given as function name \"{}\".", w.original.italic()), Some(w.clone()))),
+        },
+    };
+
+    let mut args = Vec::new();
+    let mut i = 2;
+    loop {
+        match input.get(i) {
+            None => return Err(("Expressions ended abruptly!".to_string(), Some(input[0].clone()))),
+            Some(w) if w.rtoken == rtoken::Rtoken::FunctionEnd => break,
+            Some(_) => {
+                let (arg, end) = parse_expression(&input[i..], &input[i-1])?;
+                args.push(arg);
+                i += end;
+            },
+        }
+    }
+    // Skipping the closing bracket.
+    i += 1;
+
+    let mut on_fail = None;
+    if let Some(w) = input.get(i) {
+        if w.rtoken == rtoken::Rtoken::OnFunctionFail {
+            i += 1;
+            match input.get(i) {
+                None => return Err(("No lable literal or a function call after function onfail token".to_string(), Some(w.clone()))),
+                Some(alternative) => match &alternative.rtoken {
+                    rtoken::Rtoken::FunctionStart => {
+                        let (call, end) = parse_call(&input[i..])?;
+                        on_fail = Some(Box::new(OnFail::Call(call)));
+                        i += end;
+                    },
+                    rtoken::Rtoken::LableLiteral(lable) => {
+                        on_fail = Some(Box::new(OnFail::Jump(lable.clone(), alternative.clone())));
+                        i += 1;
+                    },
+                    _ => return Err((format!("Token \"{}\" cannot be used with \"{}\"!", alternative.original, w.original),
+                                     Some(alternative.clone()))),
+                },
+            }
+        }
+    }
+
+    Ok((Call { function, args, on_fail }, i))
+}
+
+/// # Description
+/// Returns precedence of an infix operator. Operators with higher precedence are resolved first.
+///
+/// # Returns
+/// - `Some`: Precedence of the operator.
+/// - `None`: `word` is not an infix operator.
+fn infix_precedence(word: &word::Word) -> Option<usize> {
+    if let rtoken::Rtoken::Plain(op) = &word.rtoken {
+        return match &op[..] {
+            "or"                                   => Some(1),
+            "and"                                  => Some(2),
+            "=" | "=!" | "<" | ">" | "<=" | ">="   => Some(3),
+            "+" | "-"                              => Some(4),
+            "*" | "/" | "%"                        => Some(5),
+            _                                      => None,
+        };
+    }
+
+    None
+}
+
+/// # Description
+/// Parses a single operand of an infix expression. That is a value, a function call, an expression
+/// in brackets or an operand prefixed with an unary minus.
+///
+/// # Args
+/// - `input`: Words starting with the operand.
+/// - `previous`: Word right before `input` used for reporting a missing operand.
+///
+/// # Returns
+/// - `Ok`:
+///     - `Expression`: The operand.
+///     - `usize`: Amount of words taken by the operand.
+/// - `Err`: The words don't form a valid operand.
+fn parse_infix_operand(input: &[word::Word], previous: &word::Word) -> Result<(Expression, usize), SyntaxError> {
+    let first = match input.first() {
+        Some(w) => w,
+        None => return Err(("Expression ends abruptly!".to_string(), Some(previous.clone()))),
+    };
+
+    match &first.rtoken {
+        rtoken::Rtoken::InfixEnd | rtoken::Rtoken::FunctionEnd => {
+            Err((format!("Missing operand before \"{}\"!", first.original.italic()), Some(first.clone())))
+        },
+        // Unary minus is resolved as subtraction from zero.
+        rtoken::Rtoken::Plain(op) if op == "-" => {
+            let (operand, end) = parse_infix_operand(&input[1..], first)?;
+            let zero = Expression::Literal(var::Var::z(Zero::zero()).unwrap(), first.clone());
+            Ok((Expression::Call(Call { function: first.clone(), args: vec!(zero, operand), on_fail: None }), end+1))
+        },
+        // Brackets not followed by a function name only group the expression inside of them.
+        rtoken::Rtoken::FunctionStart if input.len() > 1 && !matches!(input[1].rtoken, rtoken::Rtoken::Plain(_)) => {
+            let (expression, end) = parse_infix(&input[1..], 0, first)?;
+            match input.get(end+1) {
+                Some(word) if word.rtoken == rtoken::Rtoken::FunctionEnd => Ok((expression, end+2)),
+                Some(word) => Err((format!("Expected an operator or \"{}\" but found \"{}\"!", ")".italic(), word.original.italic()),
+                                   Some(word.clone()))),
+                None => Err(("Expression ends abruptly!".to_string(), Some(first.clone()))),
+            }
+        },
+        _ => parse_expression(input, previous),
+    }
+}
+
+/// # Description
+/// Parses an infix expression containing only operators with precedence of at least `min_precedence`.
+///
+/// # Args
+/// - `input`: Words starting with the expression.
+/// - `min_precedence`: Operators with lower precedence end the expression.
+/// - `previous`: Word right before `input` used for reporting a missing operand.
+///
+/// # Returns
+/// - `Ok`:
+///     - `Expression`: The expression.
+///     - `usize`: Amount of words taken by the expression.
+/// - `Err`: The words don't form a valid expression.
+fn parse_infix(input: &[word::Word], min_precedence: usize, previous: &word::Word) -> Result<(Expression, usize), SyntaxError> {
+    let (mut lhs, mut i) = parse_infix_operand(input, previous)?;
+
+    while i < input.len() {
+        let precedence = match infix_precedence(&input[i]) {
+            Some(p) if p >= min_precedence => p,
+            _ => break,
+        };
+
+        // Operators are left associative.
+        let (rhs, end) = parse_infix(&input[i+1..], precedence+1, &input[i])?;
+        lhs = Expression::Call(Call { function: input[i].clone(), args: vec!(lhs, rhs), on_fail: None });
+        i += 1 + end;
+    }
+
+    Ok((lhs, i))
+}
+
+/// # Description
+/// Parses an infix expression written in square brackets like `[1 + 2 * $x]`. Operators
+/// become calls of primitive functions with the same name.
+///
+/// # Args
+/// - `input`: Words starting right after the opening bracket.
+/// - `bracket`: The opening bracket.
+///
+/// # Returns
+/// - `Ok`:
+///     - `Expression`: The expression.
+///     - `usize`: Amount of words taken by the expression including the closing bracket.
+/// - `Err`: The words don't form a valid expression.
+fn parse_infix_expression(input: &[word::Word], bracket: &word::Word) -> Result<(Expression, usize), SyntaxError> {
+    let (expression, end) = parse_infix(input, 0, bracket)?;
+
+    match input.get(end) {
+        Some(word) if word.rtoken == rtoken::Rtoken::InfixEnd => Ok((expression, end+1)),
+        Some(word) => Err((format!("Expected an operator or \"{}\" but found \"{}\"!", "]".italic(), word.original.italic()),
+                           Some(word.clone()))),
+        None => Err(("Expression ends abruptly!".to_string(), Some(bracket.clone()))),
+    }
+}
+
+impl Expression {
+    /// Calls `f` with every lable literal in the expression and every lable a failed call
+    /// can jump to.
+    pub fn for_each_lable<'a>(&'a self, f: &mut impl FnMut(&'a str, &'a word::Word)) {
+        match self {
+            Expression::Literal(var::Var::L(lable), word) => f(lable, word),
+            Expression::Literal(_, _) | Expression::Variable(_, _) => {},
+            Expression::Call(call) => call.for_each_lable(f),
+            Expression::Text(parts) => {
+                for part in parts {
+                    if let TextPart::Expression(expression) = part {
+                        expression.for_each_lable(f);
+                    }
+                }
+            },
+            Expression::List(items) => {
+                for item in items {
+                    item.for_each_lable(f);
+                }
+            },
+            Expression::Map(pairs, _) => {
+                for (key, value) in pairs {
+                    key.for_each_lable(f);
+                    value.for_each_lable(f);
+                }
+            },
+        }
+    }
+}
+
+impl Call {
+    /// Calls `f` with every lable literal in the call and every lable it can jump to.
+    pub fn for_each_lable<'a>(&'a self, f: &mut impl FnMut(&'a str, &'a word::Word)) {
+        for arg in &self.args {
+            arg.for_each_lable(f);
+        }
+        match self.on_fail.as_deref() {
+            Some(OnFail::Call(call)) => call.for_each_lable(f),
+            Some(OnFail::Jump(lable, word)) => f(lable, word),
+            None => {},
+        }
+    }
+
+    /// Name of the called function.
+    pub fn name(&self) -> &str {
+        match &self.function.rtoken {
+            rtoken::Rtoken::Plain(name) => name,
+            _ => "",
+        }
+    }
+}
+
+impl Statement {
+    /// Whether the statement opens a block of the given kind like "if".
+    pub fn starts_block(&self, kind: &str) -> bool {
+        matches!(self, Statement::BlockStart(k, _) if k == kind)
+    }
+
+    /// Whether the statement opens a loop, so "break" and "continue" apply to it.
+    pub fn starts_loop(&self) -> bool {
+        self.starts_block("while") || self.starts_block("for")
+    }
+
+    /// Expression of the statement if it has one.
+    pub fn expression(&self) -> Option<&Expression> {
+        match self {
+            Statement::Expression(_, expression) | Statement::Return(expression) => Some(expression),
+            Statement::BlockStart(_, condition) | Statement::BlockChain(_, condition) => condition.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+
+#[cfg(test)]
+fn parse(source: &str) -> Result<Statement, String> {
+    match super::parse_file(&mut std::io::Cursor::new(source), "", 0, false) {
+        super::ParseResult::Ok(line, _) => Ok(line.statement),
+        super::ParseResult::Err(e, _, _, _) => Err(e),
+        super::ParseResult::Eof => Ok(Statement::Empty),
+    }
+}
+
+#[test]
+fn test_infix_precedence() {
+    let expression = match parse("[1 + 2 * - $x]\n") {
+        Ok(Statement::Expression(None, Expression::Call(call))) => call,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(call_names(&expression), "+(1, *(2, -(0, $x)))");
+}
+
+#[test]
+fn test_infix_grouping() {
+    let names = |source: &str| match parse(source) {
+        Ok(Statement::Expression(None, Expression::Call(call))) => call_names(&call),
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(names("[(1 + 2) * 3 - 4 - 5]\n"), "-(-(*(+(1, 2), 3), 4), 5)");
+    assert_eq!(names("[1 < 2 and $x =! 3 or 0]\n"), "or(and(<(1, 2), =!($x, 3)), 0)");
+    assert_eq!(names("[(+ 1 2) % [3 / 4]]\n"), "%(+(1, 2), /(3, 4))");
+}
+
+#[test]
+fn test_infix_errors() {
+    // Error message and the column of the word it points to.
+    let error = |source: &str| match super::parse_file(&mut std::io::Cursor::new(source), "", 0, false) {
+        super::ParseResult::Err(e, _, _, Some(word)) => (e, word.column),
+        _ => panic!("\"{}\" was accepted.", source),
+    };
+    let (e, column) = error("[1 +]\n");
+    assert!(e.contains("Missing operand") && column == 4);
+    let (e, column) = error("[1 2]\n");
+    assert!(e.contains("Expected an operator") && column == 3);
+    let (e, column) = error("[(1 + 2) 3]\n");
+    assert!(e.contains("Expected an operator") && column == 9);
+    let (e, column) = error("[- ]\n");
+    assert!(e.contains("Missing operand") && column == 3);
+    let (e, column) = error("[(1 + 2]\n");
+    assert!(e.contains("before a closing") && column == 7);
+    let (e, column) = error("(f 1))\n");
+    assert!(e.contains("without an opening") && column == 5);
+}
+
+#[test]
+fn test_on_fail_chain() {
+    let call = match parse("Nat a: (/ 1 0) else (nope) else !end\n") {
+        Ok(Statement::Expression(Some(_), Expression::Call(call))) => call,
+        other => panic!("{:?}", other),
+    };
+    match call.on_fail.as_deref() {
+        Some(OnFail::Call(replacement)) => {
+            assert_eq!(replacement.name(), "nope");
+            assert!(matches!(replacement.on_fail.as_deref(), Some(OnFail::Jump(l, _)) if l == "end"));
+        },
+        other => panic!("{:?}", other),
+    }
+
+    assert!(parse("(+ 1 2) else 3\n").is_err());
+    assert!(parse("(+ 1 2) 3\n").is_err());
+    assert!(parse("<if\n").is_err());
+    assert!(parse("<loop 1\n").is_err());
+}
+
+#[test]
+fn test_constant() {
+    assert!(matches!(parse("const Nat max: 10\n"),
+                     Ok(Statement::Expression(Some(assign::Assign::Const(_, name)), Expression::Literal(_, _))) if name == "max"));
+    assert!(parse("const max: 10\n").is_err());
+    assert!(parse("const (+ 1 2)\n").is_err());
+}
+
+#[cfg(test)]
+fn call_names(call: &Call) -> String {
+    let args: Vec<String> = call.args.iter().map(|arg| match arg {
+        Expression::Call(c) => call_names(c),
+        Expression::Variable(_, w) => w.original.clone(),
+        Expression::Literal(v, _) => v.plain_string(),
+        Expression::Text(_) | Expression::List(_) | Expression::Map(_, _) => String::new(),
+    }).collect();
+    format!("{}({})", call.name(), args.join(", "))
+}
@@ -1,19 +1,20 @@
 //! Runk token
 //! Used tokenizing runk source code.
 
-use crate::structs::var;
+use crate::structs::{ var, word };
 use derive_more::Display;
 
 /// Runk token
 /// Used tokenizing runk source code.
-#[derive(Clone, PartialEq, PartialOrd, Debug, Display)]
+#[derive(Clone, PartialEq, Debug, Display)]
 pub enum Rtoken {
     /// Assignment operator. Usually: ":"
     #[display(fmt = "Assign ")]
     Assign,
     /// One of runk's datatypes. Usually: "N" (for natural number), "Z" (for int)...
+    /// Boxed so tokens (and errors holding them) stay small.
     #[display(fmt = "Data Type ")]
-    DataType(var::Var),
+    DataType(Box<var::Var>),
     /// Token indicating start of function. Usually: "("
     #[display(fmt = "Function Start")]
     FunctionStart,
@@ -24,9 +25,48 @@ pub enum Rtoken {
     /// executed should the function on it's left fail. Usually: "->"
     #[display(fmt = "OnFunction Fail")]
     OnFunctionFail,
+    /// Token indicating start of an infix expression. Usually: "["
+    #[display(fmt = "Infix Start")]
+    InfixStart,
+    /// Token indicating end of an infix expression. Usually: "]"
+    #[display(fmt = "Infix End")]
+    InfixEnd,
+    /// Token indicating start of a list literal. Usually: "{"
+    #[display(fmt = "List Start")]
+    ListStart,
+    /// Token indicating end of a list literal. Usually: "}"
+    #[display(fmt = "List End")]
+    ListEnd,
+    /// Token indicating start of a block spanning multiple lines.
+    /// Holds the kind of the block. Usually: "<fn"
+    #[display(fmt = "Block Start")]
+    BlockStart(String),
+    /// Token ending one part of a block and starting the next one.
+    /// Holds the kind of the next part. Usually: "><else"
+    #[display(fmt = "Block Chain")]
+    BlockChain(String),
+    /// Token indicating end of a block. A lone ">" on a line.
+    #[display(fmt = "Block End")]
+    BlockEnd,
+    /// Leaves a function with the value of the expression on it's right. Usually: "ret"
+    #[display(fmt = "Return")]
+    Return,
+    /// Leaves the innermost loop. Usually: "break"
+    #[display(fmt = "Break")]
+    Break,
+    /// Skips to the next iteration of the innermost loop. Usually: "continue"
+    #[display(fmt = "Continue")]
+    Continue,
+    /// Makes the declaration on it's right a constant. Usually: "const"
+    #[display(fmt = "Const")]
+    Const,
     /// A text value boredered by """ on both sides.
     #[display(fmt = "Text Literal")]
     TextLiteral(String),
+    /// A text literal containing variable references or expressions
+    /// that are resolved each time the literal is used.
+    #[display(fmt = "Interpolated Text")]
+    InterpolatedText(Vec<TextPart>),
     /// A value of a (hopefully defined) lable somewhere in the code.
     /// A lable literal has a "!" on the left with no spaces
     #[display(fmt = "LableLiteral")]
@@ -42,3 +82,13 @@ pub enum Rtoken {
     #[display(fmt = "Plain")]
     Plain(String),
 }
+
+/// Part of an interpolated text literal.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TextPart {
+    /// Text that is used as it is.
+    Text(String),
+    /// Words of an expression whose value is inserted into the text.
+    /// Usually: "$name", "$(...)" or "$[...]"
+    Expression(Vec<word::Word>),
+}
@@ -1,8 +1,5 @@
 pub use colored::Colorize;
-use std::process;
-use crate::structs::word;
-
-use crate::structs::source_info;
+use crate::structs::runk_error;
 
 #[macro_export]
 macro_rules! color_print {
@@ -13,22 +10,27 @@ macro_rules! color_print {
     }
 }
 
-pub fn fatal_error(info: &source_info::SourceInfo, message: String, opt_word: Option<&word::Word>) -> ! {
+/// # Description
+/// Prints an error to stderr together with the line where it happened and
+/// marks the problematic word if there is one.
+///
+/// # Arguments
+/// - `error`: The error to print.
+pub fn print_error(error: &runk_error::RunkError) {
 
     let red_border = "\t| ".to_string().red();
 
-    color_print!("\nFatal Error: ", red bold);
-    eprintln!("{}:{}: {}", info.file_name.bold(), info.line_number.to_string().bold(), message);
+    color_print!("\nError: ", red bold);
+    eprintln!("{}:{}: {}", error.file_name.bold(), error.line_number.to_string().bold(), error.message);
 
 
-    if opt_word.is_some() {
+    if error.word.is_some() {
         eprintln!("{}", &red_border);
     }
 
-    eprintln!("{}{}{}", &info.line_number.to_string().red(), &red_border, &info.original);
+    eprintln!("{}{}{}", &error.line_number.to_string().red(), &red_border, &error.original);
 
-    if opt_word.is_some() {
-        let word = opt_word.unwrap();
+    if let Some(word) = &error.word {
         eprint!("{}", &red_border);
         for _i in 0..word.column {
             eprint!(" ");
@@ -39,6 +41,4 @@ pub fn fatal_error(info: &source_info::SourceInfo, message: String, opt_word: Op
         eprintln!("");
     }
 
-    process::exit(1);
-
 }
@@ -5,3 +5,7 @@ pub mod func;
 pub mod assign;
 pub mod program_data;
 pub mod source_info;
+pub mod runk_error;
+pub mod slots;
+pub mod scopes;
+pub mod decimal;
@@ -1,10 +1,15 @@
 use super::var::Var;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub enum Assign {
     Dec(
         Var, // Type
         String, // Name
     ),
     Nondec(String),
+    /// Declaration of a variable that cannot be assigned to again.
+    Const(
+        Var, // Type
+        String, // Name
+    ),
 }
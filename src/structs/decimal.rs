@@ -0,0 +1,139 @@
+//! Scale and rounding of decimal numbers.
+//!
+//! Adding, subtracting and multiplying decimals is exact, only division (and explicit
+//! rounding) has to throw digits away. How many digits are kept after the decimal point
+//! of a quotient and how it's rounded is set for the whole program by the `scale` primitive.
+//! Each program keeps its own context, which is installed on the thread while it runs.
+
+use std::cell::Cell;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{ Zero, One, Signed };
+
+/// Ways of rounding a number to a given scale.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Rounding {
+    /// To the nearest number, ties go to the even one. Ex: 0.125 -> 0.12, 0.135 -> 0.14
+    HalfEven,
+    /// To the nearest number, ties go away from zero. Ex: 0.125 -> 0.13, -0.125 -> -0.13
+    HalfUp,
+    /// Towards negative infinity. Ex: -0.121 -> -0.13
+    Floor,
+    /// Towards positive infinity. Ex: 0.121 -> 0.13
+    Ceil,
+}
+
+impl Rounding {
+    /// Rounding mode written like in runk code. Ex: "half-even"
+    pub fn from_name(name: &str) -> Result<Rounding, String> {
+        match name {
+            "half-even" => Ok(Rounding::HalfEven),
+            "half-up" => Ok(Rounding::HalfUp),
+            "floor" => Ok(Rounding::Floor),
+            "ceil" => Ok(Rounding::Ceil),
+            _ => Err(format!("Unknown rounding \"{}\"! Use one of: half-even, half-up, floor, ceil.", name)),
+        }
+    }
+
+    /// Returns numerator / denominator rounded to a whole number. Denominator has to be positive.
+    pub fn divide(&self, numerator: &BigInt, denominator: &BigInt) -> BigInt {
+        let (quotient, remainder) = numerator.div_mod_floor(denominator);
+        if remainder.is_zero() {
+            return quotient;
+        }
+        let twice: BigInt = &remainder * 2;
+        let up = match self {
+            Rounding::Floor => false,
+            Rounding::Ceil => true,
+            Rounding::HalfUp if twice == *denominator => !numerator.is_negative(),
+            Rounding::HalfEven if twice == *denominator => quotient.is_odd(),
+            Rounding::HalfUp | Rounding::HalfEven => twice > *denominator,
+        };
+        match up {
+            true => quotient + BigInt::one(),
+            false => quotient,
+        }
+    }
+}
+
+/// Scale and rounding used when dividing decimals.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Context {
+    /// Number of digits after the decimal point.
+    pub scale: u32,
+    pub rounding: Rounding,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Context { scale: 10, rounding: Rounding::HalfEven }
+    }
+}
+
+thread_local! {
+    // Arithmetic operators of `Var` have no access to program data, so the context of
+    // the running program is installed here. See `ProgramData::with_decimal_context`.
+    static CONTEXT: Cell<Context> = Cell::new(Context::default());
+}
+
+/// Context used by divisions on this thread.
+pub fn context() -> Context {
+    CONTEXT.with(|c| c.get())
+}
+
+/// Changes the context used by divisions on this thread.
+pub fn set_context(context: Context) {
+    CONTEXT.with(|c| c.set(context));
+}
+
+/// Returns 10 to the power of `exp`.
+pub fn pow10(exp: u32) -> BigInt {
+    num_traits::pow(BigInt::from(10), exp as usize)
+}
+
+/// Returns the number of digits after the decimal point needed to write a fraction with a
+/// positive `denominator` exactly or `None` if it has no exact decimal form (like 1/3).
+pub fn exact_scale(denominator: &BigInt) -> Option<u32> {
+    let twos = denominator.trailing_zeros()?;
+    let mut rest = denominator >> twos;
+    let mut fives = 0;
+    while rest.is_multiple_of(&BigInt::from(5)) {
+        rest /= 5;
+        fives += 1;
+    }
+
+    match rest.is_one() {
+        true => u32::try_from(twos.max(fives)).ok(),
+        false => None,
+    }
+}
+
+
+// ==========
+// Unit tests
+// ==========
+#[test]
+fn test_rounding() {
+    let round = |rounding: Rounding, n: i32| rounding.divide(&BigInt::from(n), &BigInt::from(10));
+    assert_eq!(round(Rounding::HalfEven, 25), BigInt::from(2));
+    assert_eq!(round(Rounding::HalfEven, 35), BigInt::from(4));
+    assert_eq!(round(Rounding::HalfEven, -25), BigInt::from(-2));
+    assert_eq!(round(Rounding::HalfUp, 25), BigInt::from(3));
+    assert_eq!(round(Rounding::HalfUp, -25), BigInt::from(-3));
+    assert_eq!(round(Rounding::HalfUp, 24), BigInt::from(2));
+    assert_eq!(round(Rounding::Floor, -21), BigInt::from(-3));
+    assert_eq!(round(Rounding::Ceil, 21), BigInt::from(3));
+    assert_eq!(round(Rounding::Ceil, -29), BigInt::from(-2));
+    assert!(Rounding::from_name("up").is_err());
+}
+
+#[test]
+fn test_exact_scale() {
+    let scale = |d: i32| exact_scale(&BigInt::from(d));
+    assert_eq!(scale(1), Some(0));
+    assert_eq!(scale(4), Some(2));
+    assert_eq!(scale(8), Some(3));
+    assert_eq!(scale(50), Some(2));
+    assert_eq!(scale(3), None);
+    assert_eq!(scale(12), None);
+}
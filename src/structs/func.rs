@@ -1,19 +1,58 @@
+use std::rc::Rc;
+use std::cell::RefCell;
 use super::var;
 
 pub mod primitives;
 
 // Structs
 pub mod func_return;
+pub mod user_func;
 
 /// Struct representing a runk function.
 #[derive(Clone)]
 pub struct Func {
     // Closure
-    pub func: fn(&[var::Var]) -> func_return::FuncReturn,
+    pub func: FuncBody,
     // args
     pub args: ArgSpec,
 }
 
+/// Code that gets executed when a function is called.
+#[derive(Clone)]
+pub enum FuncBody {
+    /// Function built into the interpreter.
+    Primitive(fn(&[var::Var]) -> func_return::FuncReturn),
+    /// Function defined in runk source code.
+    User(Rc<user_func::UserFunc>),
+    /// Function registered by a program embedding runk. Unlike primitives it can
+    /// keep its own state.
+    Native(Rc<NativeClosure>),
+}
+
+/// Closure of a function registered by a program embedding runk.
+pub type NativeClosure = RefCell<dyn FnMut(&[var::Var]) -> func_return::FuncReturn>;
+
+impl Func {
+    /// # Description
+    /// Creates a function from a closure so it can be added to `ProgramData::funcs`.
+    ///
+    /// # Arguments
+    /// - `args`: Arguments the function accepts. They are converted before the closure is called.
+    /// - `closure`: Code of the function.
+    pub fn native(args: ArgSpec, closure: impl FnMut(&[var::Var]) -> func_return::FuncReturn + 'static) -> Func {
+        Func {
+            func: FuncBody::Native(Rc::new(RefCell::new(closure))),
+            args,
+        }
+    }
+
+    /// Returns true if the function is built into the interpreter or provided by
+    /// the embedding program, those can't be redefined by runk code.
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self.func, FuncBody::User(_))
+    }
+}
+
 /// Enum for specifiing arguments to a function.
 #[derive(Clone)]
 pub enum ArgSpec {
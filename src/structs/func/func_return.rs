@@ -1,5 +1,4 @@
 use super::var;
-use derive_more::Display;
 use crate::structs::word;
 
 pub struct FuncReturn {
@@ -7,7 +6,16 @@ pub struct FuncReturn {
     // an error message and an optional copy of the word struct with the word belonging
     // to the name of the called function (for debug and error reporting).
     pub var: Result<var::Var, (String, Option<word::Word>)>,
-    pub jump_to: Option<String>,
+    pub jump_to: Option<Jump>,
+}
+
+/// Where the program continues after a function returns.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Jump {
+    /// Jump to a lable.
+    Lable(String),
+    /// End the program with an exit code.
+    Exit(i32),
 }
 
 impl FuncReturn {
@@ -19,6 +27,14 @@ impl FuncReturn {
         };
     }
 
+    /// Constructor for a successful return without a jump.
+    pub fn value(var: var::Var) -> Self {
+        FuncReturn {
+            var: Ok(var),
+            jump_to: None,
+        }
+    }
+
     pub fn func_error(string: String) -> Self {
         return FuncReturn {
             var: Result::Err((string, None)),
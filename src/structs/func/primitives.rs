@@ -4,10 +4,27 @@ pub mod add;
 pub mod sub;
 pub mod mul;
 pub mod div;
+pub mod rem;
+pub mod pow;
+pub mod gcd;
+pub mod lcm;
+pub mod abs;
+pub mod min;
+pub mod max;
+pub mod isqrt;
+pub mod sign;
 pub mod bin;
 pub mod not;
 pub mod and;
 pub mod or;
+pub mod band;
+pub mod bor;
+pub mod bxor;
+pub mod bnot;
+pub mod shl;
+pub mod shr;
+pub mod popcount;
+pub mod bit;
 pub mod go;
 pub mod goif;
 pub mod equal;
@@ -16,6 +33,13 @@ pub mod lesser;
 pub mod r#in;
 pub mod int;
 pub mod nat;
+pub mod rat;
+pub mod dec;
+pub mod round;
+pub mod scale;
+pub mod frombase;
+pub mod tobase;
+pub mod fmt;
 pub mod greater;
 pub mod lesser_equal;
 pub mod greater_equal;
@@ -25,6 +49,26 @@ pub mod cats;
 pub mod line;
 pub mod lines;
 pub mod exit;
+pub mod at;
+pub mod set;
+pub mod len;
+pub mod push;
+pub mod pop;
+pub mod slice;
+pub mod get;
+pub mod has;
+pub mod remove;
+pub mod keys;
+pub mod substr;
+pub mod find;
+pub mod replace;
+pub mod split;
+pub mod trim;
+pub mod upper;
+pub mod lower;
+pub mod starts;
+pub mod ends;
+pub mod repeat;
 
 impl super::super::program_data::ProgramData {
     pub fn add_primitive_functions(&mut self) {
@@ -32,6 +76,15 @@ impl super::super::program_data::ProgramData {
         self.funcs.insert(format!("-"),      sub::get_func());
         self.funcs.insert(format!("*"),      mul::get_func());
         self.funcs.insert(format!("/"),      div::get_func());
+        self.funcs.insert("%".to_string(),      rem::get_func());
+        self.funcs.insert("pow".to_string(),    pow::get_func());
+        self.funcs.insert("gcd".to_string(),    gcd::get_func());
+        self.funcs.insert("lcm".to_string(),    lcm::get_func());
+        self.funcs.insert("abs".to_string(),    abs::get_func());
+        self.funcs.insert("min".to_string(),    min::get_func());
+        self.funcs.insert("max".to_string(),    max::get_func());
+        self.funcs.insert("isqrt".to_string(),  isqrt::get_func());
+        self.funcs.insert("sign".to_string(),   sign::get_func());
         self.funcs.insert(format!("<"),      lesser::get_func());
         self.funcs.insert(format!(">"),      greater::get_func());
         self.funcs.insert(format!("="),      equal::get_func());
@@ -41,10 +94,25 @@ impl super::super::program_data::ProgramData {
         self.funcs.insert(format!("in"),     r#in::get_func());
         self.funcs.insert(format!("int"),    int::get_func());
         self.funcs.insert(format!("nat"),    nat::get_func());
+        self.funcs.insert("rat".to_string(),    rat::get_func());
+        self.funcs.insert("dec".to_string(),    dec::get_func());
+        self.funcs.insert("round".to_string(),  round::get_func());
+        self.funcs.insert("scale".to_string(),  scale::get_func());
+        self.funcs.insert("frombase".to_string(), frombase::get_func());
+        self.funcs.insert("tobase".to_string(), tobase::get_func());
+        self.funcs.insert("fmt".to_string(),    fmt::get_func());
         self.funcs.insert(format!("bin"),    bin::get_func());
         self.funcs.insert(format!("not"),    not::get_func());
         self.funcs.insert(format!("and"),    and::get_func());
         self.funcs.insert(format!("or"),     or::get_func());
+        self.funcs.insert("band".to_string(),   band::get_func());
+        self.funcs.insert("bor".to_string(),    bor::get_func());
+        self.funcs.insert("bxor".to_string(),   bxor::get_func());
+        self.funcs.insert("bnot".to_string(),   bnot::get_func());
+        self.funcs.insert("shl".to_string(),    shl::get_func());
+        self.funcs.insert("shr".to_string(),    shr::get_func());
+        self.funcs.insert("popcount".to_string(), popcount::get_func());
+        self.funcs.insert("bit".to_string(),    bit::get_func());
         self.funcs.insert(format!("go"),     go::get_func());
         self.funcs.insert(format!("goif"),   goif::get_func());
         self.funcs.insert(format!("err"),    err::get_func());
@@ -53,6 +121,26 @@ impl super::super::program_data::ProgramData {
         self.funcs.insert(format!("line"),   line::get_func());
         self.funcs.insert(format!("lines"),  lines::get_func());
         self.funcs.insert(format!("exit"),   exit::get_func());
+        self.funcs.insert("at".to_string(),     at::get_func());
+        self.funcs.insert("set".to_string(),    set::get_func());
+        self.funcs.insert("len".to_string(),    len::get_func());
+        self.funcs.insert("push".to_string(),   push::get_func());
+        self.funcs.insert("pop".to_string(),    pop::get_func());
+        self.funcs.insert("slice".to_string(),  slice::get_func());
+        self.funcs.insert("get".to_string(),    get::get_func());
+        self.funcs.insert("has".to_string(),    has::get_func());
+        self.funcs.insert("remove".to_string(), remove::get_func());
+        self.funcs.insert("keys".to_string(),   keys::get_func());
+        self.funcs.insert("substr".to_string(), substr::get_func());
+        self.funcs.insert("find".to_string(),   find::get_func());
+        self.funcs.insert("replace".to_string(), replace::get_func());
+        self.funcs.insert("split".to_string(),  split::get_func());
+        self.funcs.insert("trim".to_string(),   trim::get_func());
+        self.funcs.insert("upper".to_string(),  upper::get_func());
+        self.funcs.insert("lower".to_string(),  lower::get_func());
+        self.funcs.insert("starts".to_string(), starts::get_func());
+        self.funcs.insert("ends".to_string(),   ends::get_func());
+        self.funcs.insert("repeat".to_string(), repeat::get_func());
 
     }
 }
@@ -0,0 +1,32 @@
+use super::super::var;
+use num_traits::{ Zero, One };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Absolute value of a number.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if args.len() != 1 {
+        return func_return::FuncReturn::func_error("Function needs exactly one number!".to_string());
+    }
+
+    let zero = var::Var::new(0);
+    let mut result = match args[0] < zero {
+        true => zero - args[0].clone(),
+        false => args[0].clone(),
+    };
+    func_return::FuncReturn::value(result.best_fit())
+}
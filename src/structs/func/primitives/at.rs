@@ -0,0 +1,50 @@
+use super::super::var;
+use num_traits::{ Zero, ToPrimitive };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::a(Vec::new()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns the item of a list (first argument) at an index (second argument).
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::A(list) = &args[0] {
+        return match index(&args[1], list.len()) {
+            Ok(i) => func_return::FuncReturn::value(list[i].clone()),
+            Err(e) => func_return::FuncReturn::func_error(e),
+        };
+    }
+
+    func_return::FuncReturn::func_error("First argument has to be a list!".to_string())
+}
+
+/// # Description
+/// Converts a value to an index into a list. Indexes start at zero.
+///
+/// # Arguments
+/// - `var`: The index.
+/// - `len`: Amount of items in the list, the index has to be lower.
+///
+/// # Returns
+/// - `Err`: Description of the issue intended to be shown to the user.
+pub fn index(var: &var::Var, len: usize) -> Result<usize, String> {
+    let i = match var.get_n() {
+        Ok(var::Var::N(n)) => n.to_usize(),
+        _ => return Err(format!("Index has to be a natural number, not \"{}\"!", var)),
+    };
+
+    match i {
+        Some(i) if i < len => Ok(i),
+        _ => Err(format!("Index {} is out of range of a list with {} items!", var.plain_string(), len)),
+    }
+}
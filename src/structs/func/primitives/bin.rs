@@ -6,7 +6,7 @@ use super::super::super::func;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Limited(
             vec!(
                 var::Var::z(Zero::zero()).unwrap(),
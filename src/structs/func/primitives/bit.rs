@@ -0,0 +1,31 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns 1 if the bit of an integer (first argument) at a position (second argument)
+/// is set or 0 otherwise. Position 0 is the lowest bit, negative numbers are in two's complement.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::Z(z), var::Var::N(position)) = (&args[0], &args[1]) {
+        return match u64::try_from(position) {
+            Ok(p) => func_return::FuncReturn::value(var::Var::new(z.bit(p) as u8)),
+            // Only the sign is left that far.
+            Err(_e) => func_return::FuncReturn::value(var::Var::new((z.sign() == num_bigint::Sign::Minus) as u8)),
+        };
+    }
+
+    func_return::FuncReturn::func_error("Function needs an integer and a position of a bit!".to_string())
+}
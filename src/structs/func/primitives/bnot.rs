@@ -0,0 +1,25 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Flips all bits of an integer in two's complement, so the result is -n - 1.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::Z(z) = &args[0] {
+        return func_return::FuncReturn::value(var::Var::Z(!z).best_fit());
+    }
+
+    func_return::FuncReturn::func_error("Argument has to be an integer!".to_string())
+}
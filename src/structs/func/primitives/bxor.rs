@@ -0,0 +1,34 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Bitwise XOR of all integers. Negative numbers are in two's complement.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    let mut result: Option<num_bigint::BigInt> = None;
+    for arg in args {
+        if let var::Var::Z(z) = arg {
+            result = Some(match result {
+                Some(r) => r ^ z,
+                None => z.clone(),
+            });
+        }
+    }
+
+    match result {
+        Some(r) => func_return::FuncReturn::value(var::Var::Z(r).best_fit()),
+        None => func_return::FuncReturn::func_error("Function needs at least one integer!".to_string()),
+    }
+}
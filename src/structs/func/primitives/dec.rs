@@ -0,0 +1,27 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Converts text like "12.50" or "3" to a decimal number, keeping the digits after the decimal point.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::T(t) = &args[0] {
+        return match var::Var::num_from_str(t.trim()).and_then(|v| v.get_d()) {
+            Ok(v) => func_return::FuncReturn::value(v),
+            Err(_e) => func_return::FuncReturn::func_error(format!("\"{}\" is not a number!", args[0])),
+        };
+    }
+
+    func_return::FuncReturn::func_error(format!("Cannot convert \"{}\" to a number!", args[0]))
+}
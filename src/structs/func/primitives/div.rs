@@ -1,5 +1,5 @@
 use super::super::var;
-use num_traits::{ Zero };
+use num_traits::{ Zero, One };
 use super::super::func_return;
 use super::super::Func;
 use super::super::super::func;
@@ -7,11 +7,13 @@ use super::super::super::func;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Unlimited(
             vec!(
                 var::Var::z(Zero::zero()).unwrap(),
-                var::Var::n(Zero::zero()).unwrap()
+                var::Var::n(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap()
             )
         ),
     }
@@ -26,9 +28,7 @@ pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
             sum = arg.clone();
         }
         else {
-            // TODO prevent division by 0
-            let zero: num_bigint::BigInt = Zero::zero();
-            if arg.clone().to_z().unwrap() == var::Var::Z(zero) {
+            if *arg == var::Var::z(Zero::zero()).unwrap() {
                 return func_return::FuncReturn::func_error(format!("Division by zero!"));
             }
             sum /= arg.clone();
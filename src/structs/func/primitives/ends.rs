@@ -0,0 +1,25 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns 1 if a text (first argument) ends with a text (second argument) or 0 otherwise.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::T(text), var::Var::T(part)) = (&args[0], &args[1]) {
+        return func_return::FuncReturn::value(var::Var::new(text.ends_with(&part[..]) as u8));
+    }
+
+    func_return::FuncReturn::func_error("Both arguments have to be text!".to_string())
+}
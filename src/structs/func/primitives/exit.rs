@@ -4,11 +4,10 @@ use num_traits::ToPrimitive;
 use super::super::func_return;
 use super::super::Func;
 use super::super::super::func;
-use std::process;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Unlimited(
             vec!(
                 var::Var::z(Zero::zero()).unwrap(),
@@ -26,7 +25,10 @@ pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
             Some(i) => i,
             None => return func_return::FuncReturn::func_error(format!("Number is to big to be an exit code!")),
         };
-        process::exit(code);
+        return func_return::FuncReturn {
+            var: Ok(var::Var::n(Zero::zero()).unwrap()),
+            jump_to: Some(func_return::Jump::Exit(code)),
+        };
     }
     panic!("Internal error in funtion exit");
 }
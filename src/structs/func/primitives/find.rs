@@ -0,0 +1,30 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns the index of the first character of the first occurrence of a text (second
+/// argument) in a text (first argument). Fails if it doesn't occur.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::T(text), var::Var::T(pattern)) = (&args[0], &args[1]) {
+        return match text.find(&pattern[..]) {
+            // Counting characters, not bytes.
+            Some(byte) => func_return::FuncReturn::value(var::Var::new(text[..byte].chars().count())),
+            None => func_return::FuncReturn::func_error(format!("\"{}\" was not found in \"{}\"!", pattern, text)),
+        };
+    }
+
+    func_return::FuncReturn::func_error("Both arguments have to be text!".to_string())
+}
@@ -0,0 +1,113 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::d(Zero::zero(), 0).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Largest width a format can ask for.
+const MAX_WIDTH: usize = u16::MAX as usize;
+
+/// How a number is written, parsed from text like "+010,".
+struct Spec {
+    /// Write "+" before positive numbers.
+    plus: bool,
+    /// Pad with zeros after the sign instead of spaces before it.
+    zeros: bool,
+    /// Minimal length of the text.
+    width: usize,
+    /// Character separating thousands.
+    separator: Option<char>,
+}
+
+impl Spec {
+    fn parse(spec: &str) -> Result<Spec, String> {
+        let error = || format!("Invalid format \"{}\"! Expected an optional \"+\", \"0\", width and \",\" or \"_\".", spec);
+        let mut rest = spec;
+        let plus = rest.starts_with('+');
+        rest = rest.strip_prefix('+').unwrap_or(rest);
+        let separator = rest.chars().last().filter(|c| *c == ',' || *c == '_');
+        if separator.is_some() {
+            rest = &rest[..rest.len()-1];
+        }
+        let zeros = rest.starts_with('0');
+        let width = match rest {
+            "" => 0,
+            _ => rest.parse::<usize>().map_err(|_e| error())?,
+        };
+        if !rest.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+        if width > MAX_WIDTH {
+            return Err(format!("Width {} in format \"{}\" is larger than {}!", width, spec, MAX_WIDTH));
+        }
+
+        Ok(Spec { plus, zeros, width, separator })
+    }
+}
+
+/// Inserts `separator` between every three digits from the right.
+fn group(digits: &str, separator: Option<char>) -> String {
+    let separator = match separator {
+        Some(s) => s,
+        None => return digits.to_string(),
+    };
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// Writes a number (first argument) as text formatted by the second argument. The format is
+/// an optional "+" to show the sign of positive numbers, a minimal width (starting with "0" to
+/// pad with zeros) and an optional "," or "_" separating thousands. Ex: "+08,"
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    let spec = match &args[1] {
+        var::Var::T(t) => match Spec::parse(t) {
+            Ok(s) => s,
+            Err(e) => return func_return::FuncReturn::func_error(e),
+        },
+        _ => return func_return::FuncReturn::func_error("Format has to be text!".to_string()),
+    };
+
+    let plain = args[0].plain_string();
+    let (sign, unsigned) = match plain.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None if spec.plus => ("+", &plain[..]),
+        None => ("", &plain[..]),
+    };
+    let (mut whole, decimals) = match unsigned.split_once('.') {
+        Some((w, d)) => (w.to_string(), format!(".{}", d)),
+        None => (unsigned.to_string(), String::new()),
+    };
+
+    // Length of the text when the whole part has `digits` digits.
+    let length = |digits: usize| match spec.separator {
+        Some(_) => sign.len() + digits + digits.saturating_sub(1) / 3 + decimals.len(),
+        None => sign.len() + digits + decimals.len(),
+    };
+    if spec.zeros {
+        let mut digits = whole.len();
+        while length(digits) < spec.width {
+            digits += 1;
+        }
+        whole.insert_str(0, &"0".repeat(digits - whole.len()));
+    }
+    let text = format!("{}{}{}", sign, group(&whole, spec.separator), decimals);
+    func_return::FuncReturn::value(var::Var::t(format!("{:>width$}", text, width = spec.width)).unwrap())
+}
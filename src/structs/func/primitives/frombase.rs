@@ -0,0 +1,48 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Converts text (first argument) with a number written in a base from 2 to 36 (second
+/// argument) to the number. Letters are digits above 9 regardless of their case.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::T(text), var::Var::N(_n)) = (&args[0], &args[1]) {
+        let base = match base(&args[1]) {
+            Ok(b) => b,
+            Err(e) => return func_return::FuncReturn::func_error(e),
+        };
+        let text = text.trim();
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(base)) {
+            return func_return::FuncReturn::func_error(format!("\"{}\" is not a number in base {}!", text, base));
+        }
+        let value = num_bigint::BigInt::parse_bytes(digits.as_bytes(), base).unwrap();
+        return func_return::FuncReturn::value(var::Var::new(if negative { -value } else { value }));
+    }
+
+    func_return::FuncReturn::func_error("Function needs text and a base!".to_string())
+}
+
+/// Returns the base in `var` if it is from 2 to 36.
+pub fn base(var: &var::Var) -> Result<u32, String> {
+    match var.get_n() {
+        Ok(var::Var::N(n)) if n >= 2.into() && n <= 36.into() => Ok(u32::try_from(&n).unwrap()),
+        _ => Err(format!("Base {} has to be from 2 to 36!", var.plain_string())),
+    }
+}
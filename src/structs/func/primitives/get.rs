@@ -0,0 +1,28 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::m(Default::default()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns the value stored in a map (first argument) under a key (second argument).
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::M(map), var::Var::T(key)) = (&args[0], &args[1]) {
+        return match map.get(key) {
+            Some(value) => func_return::FuncReturn::value(value.clone()),
+            None => func_return::FuncReturn::func_error(format!("Key \"{}\" is not in the map!", key)),
+        };
+    }
+
+    func_return::FuncReturn::func_error("Function needs a map and a key!".to_string())
+}
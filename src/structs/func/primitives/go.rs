@@ -5,7 +5,7 @@ use super::super::super::func;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Limited(
             vec!(
                 var::Var::L(format!("")),
@@ -19,7 +19,7 @@ pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
     if let var::Var::L(s) = &args[0] {
         return func_return::FuncReturn{
             var: Ok(var::Var::T(format!(""))),
-            jump_to: Some(s.clone()),
+            jump_to: Some(func_return::Jump::Lable(s.clone())),
         };
     }
 
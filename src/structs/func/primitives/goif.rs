@@ -6,7 +6,7 @@ use super::super::super::func;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Limited(
             vec!(
                 var::Var::z(num_bigint::ToBigInt::to_bigint(&0).unwrap()).unwrap(),
@@ -30,7 +30,7 @@ pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
     if let var::Var::L(s) = &args[1] {
         return func_return::FuncReturn{
             var: Ok(var::Var::t(format!("")).unwrap()),
-            jump_to: Some(s.clone()),
+            jump_to: Some(func_return::Jump::Lable(s.clone())),
         };
     }
 
@@ -1,16 +1,16 @@
 use super::super::var;
-use num_traits::{ Zero };
+use num_traits::{ Zero, One };
 use super::super::func_return;
 use super::super::Func;
 use super::super::super::func;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Limited(
             vec!(
-                var::Var::z(Zero::zero()).unwrap(),
-                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
             )
         ),
     }
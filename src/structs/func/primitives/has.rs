@@ -0,0 +1,25 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::m(Default::default()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns 1 if a map (first argument) has a key (second argument) or 0 otherwise.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::M(map), var::Var::T(key)) = (&args[0], &args[1]) {
+        return func_return::FuncReturn::value(var::Var::new(map.contains_key(key) as u8));
+    }
+
+    func_return::FuncReturn::func_error("Function needs a map and a key!".to_string())
+}
@@ -7,7 +7,7 @@ use std::io;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Unlimited(
             vec!(
                 var::Var::z(Zero::zero()).unwrap(),
@@ -21,7 +21,9 @@ pub fn get_func() -> Func {
 pub fn op(_args: &[var::Var]) -> func_return::FuncReturn {
     let mut buffer = String::new();
     let stdin = io::stdin();
-    stdin.read_line(&mut buffer).unwrap();
+    if let Err(e) = stdin.read_line(&mut buffer) {
+        return func_return::FuncReturn::func_error(format!("Could not read from standard input: {}", e));
+    }
     // Remove trailing '\n'
     buffer.pop();
     // Remove trailing '\r' if present
@@ -2,11 +2,10 @@ use super::super::var;
 use super::super::func_return;
 use super::super::Func;
 use super::super::super::func;
-use num_bigint::BigInt;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Limited(
             vec!(
                 var::Var::t(format!("")).unwrap(),
@@ -15,22 +14,15 @@ pub fn get_func() -> Func {
     }
 }
 
+/// Converts text with a number written like in runk code, for example "-1_000" or "0xFF", to an integer.
 pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
-    if let var::Var::T(t) = args[0].clone() {
-        // Oh my god...
-        return func_return::FuncReturn{
-            var: match var::Var::z(match &t[..].parse::<BigInt>() {
-                    // Converting string to bigint
-                    Ok(bigint) => bigint.clone(),
-                    Err(_e) => return func_return::FuncReturn::func_error(format!("\"{}\" is not a number!", args[0])),
-                }
-            )
-            {
-                // Can the number fit into this type?
-                Ok(v) =>  Ok(v.clone()),
-                Err(_e) => Err((format!("Cannot convert \"{}\" to an integer!", args[0]), None))
+    if let var::Var::T(t) = &args[0] {
+        return match var::Var::num_from_str(t.trim()) {
+            Ok(v) => match v.get_z() {
+                Ok(v) => func_return::FuncReturn::value(v),
+                Err(_e) => func_return::FuncReturn::func_error(format!("Cannot convert \"{}\" to an integer!", args[0])),
             },
-            jump_to: None
+            Err(_e) => func_return::FuncReturn::func_error(format!("\"{}\" is not a number!", args[0])),
         };
     }
 
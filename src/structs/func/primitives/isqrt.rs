@@ -0,0 +1,28 @@
+use super::super::var;
+use num_traits::{ Zero, Signed };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Square root of a number rounded down to a whole number.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::Z(z) = &args[0] {
+        if z.is_negative() {
+            return func_return::FuncReturn::func_error(format!("Cannot take a square root of a negative number {}!", z));
+        }
+        return func_return::FuncReturn::value(var::Var::Z(z.sqrt()).best_fit());
+    }
+
+    func_return::FuncReturn::func_error("Argument has to be an integer!".to_string())
+}
@@ -0,0 +1,24 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::m(Default::default()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns a list of keys of a map in their order.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::M(map) = &args[0] {
+        return func_return::FuncReturn::value(var::Var::A(map.keys().map(|k| var::Var::T(k.clone())).collect()));
+    }
+
+    func_return::FuncReturn::func_error("Argument has to be a map!".to_string())
+}
@@ -0,0 +1,35 @@
+use super::super::var;
+use num_traits::{ Zero, Signed };
+use num_integer::Integer;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Least common multiple of all numbers. Zero if any of them is zero.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    let mut result: Option<num_bigint::BigInt> = None;
+    for arg in args {
+        if let var::Var::Z(z) = arg {
+            result = Some(match result {
+                Some(r) => r.lcm(z),
+                None => z.abs(),
+            });
+        }
+    }
+
+    match result {
+        Some(r) => func_return::FuncReturn::value(var::Var::Z(r).best_fit()),
+        None => func_return::FuncReturn::func_error("Function needs at least one number!".to_string()),
+    }
+}
@@ -0,0 +1,27 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::a(Vec::new()).unwrap(),
+                var::Var::m(Default::default()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns the amount of items in a list or a map or the amount of characters in a text.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    match args {
+        [var::Var::A(list)] => func_return::FuncReturn::value(var::Var::new(list.len())),
+        [var::Var::M(map)] => func_return::FuncReturn::value(var::Var::new(map.len())),
+        [var::Var::T(text)] => func_return::FuncReturn::value(var::Var::new(text.chars().count())),
+        _ => func_return::FuncReturn::func_error("Function needs a single list, map or text!".to_string()),
+    }
+}
@@ -0,0 +1,24 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Converts all letters of a text to lowercase.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::T(text) = &args[0] {
+        return func_return::FuncReturn::value(var::Var::t(text.to_lowercase()).unwrap());
+    }
+
+    func_return::FuncReturn::func_error("Argument has to be text!".to_string())
+}
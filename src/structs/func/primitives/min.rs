@@ -0,0 +1,33 @@
+use super::super::var;
+use num_traits::{ Zero, One };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns the smallest of all numbers.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    let mut result = match args.first() {
+        Some(first) => first.clone(),
+        None => return func_return::FuncReturn::func_error("Function needs at least one number!".to_string()),
+    };
+    for arg in &args[1..] {
+        if *arg < result {
+            result = arg.clone();
+        }
+    }
+
+    func_return::FuncReturn::value(result.best_fit())
+}
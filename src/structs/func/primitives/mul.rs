@@ -1,16 +1,18 @@
 use super::super::var;
-use num_traits::{ Zero };
+use num_traits::{ Zero, One };
 use super::super::func_return;
 use super::super::Func;
 use super::super::super::func;
 
 pub fn get_func() -> Func {
     Func {
-        func: op,
+        func: func::FuncBody::Primitive(op),
         args: func::ArgSpec::Unlimited(
             vec!(
                 var::Var::z(Zero::zero()).unwrap(),
-                var::Var::n(Zero::zero()).unwrap()
+                var::Var::n(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap()
             )
         ),
     }
@@ -0,0 +1,27 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::a(Vec::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns a list without its last item.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::A(list) = &args[0] {
+        if list.is_empty() {
+            return func_return::FuncReturn::func_error("Cannot pop from an empty list!".to_string());
+        }
+        return func_return::FuncReturn::value(var::Var::A(list[..list.len()-1].to_vec()));
+    }
+
+    func_return::FuncReturn::func_error("Argument has to be a list!".to_string())
+}
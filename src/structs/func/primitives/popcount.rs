@@ -0,0 +1,29 @@
+use super::super::var;
+use num_traits::{ Zero, Signed };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Number of bits set to 1 in a non-negative integer.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::Z(z) = &args[0] {
+        // In two's complement negative numbers have infinitely many ones.
+        if z.is_negative() {
+            return func_return::FuncReturn::func_error(format!("Cannot count bits of a negative number {}!", z));
+        }
+        return func_return::FuncReturn::value(var::Var::new(z.magnitude().count_ones()));
+    }
+
+    func_return::FuncReturn::func_error("Argument has to be an integer!".to_string())
+}
@@ -0,0 +1,49 @@
+use super::super::var;
+use num_traits::{ Zero, Signed };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Largest number of bits a power without a modulus is allowed to have.
+const MAX_BITS: u64 = 1 << 30;
+
+/// Raises the first number to the power of the second one. With a third number the
+/// result is the remainder after dividing the power by it, computed without the whole power.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    let numbers: Vec<&num_bigint::BigInt> = args.iter().filter_map(|a| match a {
+        var::Var::Z(z) => Some(z),
+        _ => None,
+    }).collect();
+    if numbers.len() != args.len() || args.len() < 2 || args.len() > 3 {
+        return func_return::FuncReturn::func_error("Function needs a base, an exponent and optionally a modulus!".to_string());
+    }
+
+    let (base, exponent) = (numbers[0], numbers[1]);
+    if exponent.is_negative() {
+        return func_return::FuncReturn::func_error(format!("Exponent {} can't be negative!", exponent));
+    }
+    let result = match numbers.get(2) {
+        Some(modulus) if !modulus.is_positive() => {
+            return func_return::FuncReturn::func_error(format!("Modulus {} has to be positive!", modulus));
+        },
+        Some(modulus) => base.modpow(exponent, modulus),
+        // Powers of 0, 1 and -1 never grow, others have at most this many bits.
+        None => match u32::try_from(exponent) {
+            Ok(e) if base.bits() <= 1 || base.bits().saturating_mul(u64::from(e)) <= MAX_BITS => base.pow(e),
+            _ => return func_return::FuncReturn::func_error(format!("Power of {} to {} would be too large!", base, exponent)),
+        },
+    };
+
+    func_return::FuncReturn::value(var::Var::Z(result).best_fit())
+}
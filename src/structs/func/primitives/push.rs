@@ -0,0 +1,35 @@
+use super::super::var;
+use num_traits::{ Zero, One };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        // Items can be of any type.
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::a(Vec::new()).unwrap(),
+                var::Var::m(Default::default()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+                var::Var::l(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns a list (first argument) with the other arguments added to its end.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let Some(var::Var::A(list)) = args.first() {
+        let mut list = list.clone();
+        list.extend_from_slice(&args[1..]);
+        return func_return::FuncReturn::value(var::Var::A(list));
+    }
+
+    func_return::FuncReturn::func_error("First argument has to be a list!".to_string())
+}
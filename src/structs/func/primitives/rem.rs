@@ -0,0 +1,30 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::z(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Remainder after dividing the first number by the second one. Like `/` the division
+/// rounds towards zero, so the remainder has the sign of the first number.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::Z(a), var::Var::Z(b)) = (&args[0], &args[1]) {
+        if b.is_zero() {
+            return func_return::FuncReturn::func_error("Division by zero!".to_string());
+        }
+        return func_return::FuncReturn::value(var::Var::Z(a % b).best_fit());
+    }
+
+    func_return::FuncReturn::func_error("Both arguments have to be integers!".to_string())
+}
@@ -0,0 +1,28 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::m(Default::default()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns a map (first argument) without a key (second argument). The map is returned
+/// unchanged if it doesn't have the key.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::M(map), var::Var::T(key)) = (&args[0], &args[1]) {
+        let mut map = map.clone();
+        map.remove(key);
+        return func_return::FuncReturn::value(var::Var::M(map));
+    }
+
+    func_return::FuncReturn::func_error("Function needs a map and a key!".to_string())
+}
@@ -0,0 +1,33 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Longest text in bytes the function is allowed to create.
+const MAX_LEN: usize = 1 << 30;
+
+/// Returns a text (first argument) repeated a number of times (second argument).
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::T(text), var::Var::N(count)) = (&args[0], &args[1]) {
+        let count_len = usize::try_from(count).ok().and_then(|c| Some((c, text.len().checked_mul(c)?)));
+        return match count_len {
+            Some((c, len)) if len <= MAX_LEN => func_return::FuncReturn::value(var::Var::t(text.repeat(c)).unwrap()),
+            _ => func_return::FuncReturn::func_error(format!("Cannot repeat a text {} times, the result would be too long!", count)),
+        };
+    }
+
+    func_return::FuncReturn::func_error("Function needs text and a number!".to_string())
+}
@@ -0,0 +1,30 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Replaces every occurrence of a text (second argument) in a text (first argument) with
+/// a text (third argument).
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::T(text), var::Var::T(from), var::Var::T(to)) = (&args[0], &args[1], &args[2]) {
+        if from.is_empty() {
+            return func_return::FuncReturn::func_error("Replaced text can't be empty!".to_string());
+        }
+        return func_return::FuncReturn::value(var::Var::t(text.replace(&from[..], to)).unwrap());
+    }
+
+    func_return::FuncReturn::func_error("All arguments have to be text!".to_string())
+}
@@ -0,0 +1,65 @@
+use super::super::var;
+use num_traits::{ Zero, One };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+use super::super::super::decimal;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Rounds a number (first argument) to a decimal number with a number of digits after
+/// the decimal point (second argument). Rounding (optional third argument) is "half-even"
+/// by default.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if args.len() < 2 || args.len() > 3 {
+        return func_return::FuncReturn::func_error("Function needs a number, a scale and optionally a rounding!".to_string());
+    }
+
+    let scale = match scale(&args[1]) {
+        Ok(s) => s,
+        Err(e) => return func_return::FuncReturn::func_error(e),
+    };
+    let rounding = match args.get(2).map(rounding) {
+        None => decimal::Rounding::HalfEven,
+        Some(Ok(r)) => r,
+        Some(Err(e)) => return func_return::FuncReturn::func_error(e),
+    };
+
+    match args[0].round(scale, rounding) {
+        Ok(v) => func_return::FuncReturn::value(v),
+        Err(e) => func_return::FuncReturn::func_error(e),
+    }
+}
+
+/// Largest number of digits after the decimal point a number can have.
+const MAX_SCALE: u32 = u16::MAX as u32;
+
+/// Returns the number of digits after the decimal point in `var`.
+pub fn scale(var: &var::Var) -> Result<u32, String> {
+    match var.get_n() {
+        Ok(var::Var::N(n)) => match u32::try_from(&n) {
+            Ok(s) if s <= MAX_SCALE => Ok(s),
+            _ => Err(format!("Scale {} is too large, it can be at most {}!", n, MAX_SCALE)),
+        },
+        _ => Err(format!("Scale \"{}\" has to be a natural number!", var.plain_string())),
+    }
+}
+
+/// Returns the rounding named in `var`. Ex: "half-up"
+pub fn rounding(var: &var::Var) -> Result<decimal::Rounding, String> {
+    match var {
+        var::Var::T(name) => decimal::Rounding::from_name(name),
+        _ => Err(format!("Rounding \"{}\" has to be text!", var)),
+    }
+}
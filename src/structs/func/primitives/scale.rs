@@ -0,0 +1,41 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+use super::super::super::decimal;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::n(Zero::zero()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Sets the number of digits after the decimal point (first argument) kept by divisions
+/// of decimal numbers and optionally how they are rounded (second argument).
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if args.is_empty() || args.len() > 2 {
+        return func_return::FuncReturn::func_error("Function needs a scale and optionally a rounding!".to_string());
+    }
+
+    let mut context = decimal::context();
+    context.scale = match super::round::scale(&args[0]) {
+        Ok(s) => s,
+        Err(e) => return func_return::FuncReturn::func_error(e),
+    };
+    if let Some(rounding) = args.get(1) {
+        context.rounding = match super::round::rounding(rounding) {
+            Ok(r) => r,
+            Err(e) => return func_return::FuncReturn::func_error(e),
+        };
+    }
+    decimal::set_context(context);
+
+    func_return::FuncReturn::value(var::Var::t(String::new()).unwrap())
+}
@@ -0,0 +1,54 @@
+use super::super::var;
+use num_traits::{ Zero, One };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        // Items can be of any type.
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::a(Vec::new()).unwrap(),
+                var::Var::m(Default::default()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+                var::Var::l(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns a list (first argument) with the item at an index (second argument) replaced
+/// by a value (third argument). Works the same with a map and a key, a missing key is added.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if args.len() != 3 {
+        return func_return::FuncReturn::func_error("Function needs a list or a map, an index or a key and a value!".to_string());
+    }
+
+    if let var::Var::M(map) = &args[0] {
+        let key = match &args[1] {
+            var::Var::T(key) => key.clone(),
+            _ => return func_return::FuncReturn::func_error(format!("Key of a map has to be text, not \"{}\"!", args[1])),
+        };
+        let mut map = map.clone();
+        map.insert(key, args[2].clone());
+        return func_return::FuncReturn::value(var::Var::M(map));
+    }
+
+    if let var::Var::A(list) = &args[0] {
+        let i = match super::at::index(&args[1], list.len()) {
+            Ok(i) => i,
+            Err(e) => return func_return::FuncReturn::func_error(e),
+        };
+        let mut list = list.clone();
+        list[i] = args[2].clone();
+        return func_return::FuncReturn::value(var::Var::A(list));
+    }
+
+    func_return::FuncReturn::func_error("First argument has to be a list or a map!".to_string())
+}
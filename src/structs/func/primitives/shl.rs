@@ -0,0 +1,32 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Largest number of bits an integer can be shifted to the left by.
+const MAX_SHIFT: u32 = 1 << 30;
+
+/// Shifts bits of an integer (first argument) to the left by a number of bits (second argument).
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::Z(z), var::Var::N(bits)) = (&args[0], &args[1]) {
+        return match u32::try_from(bits) {
+            Ok(b) if b <= MAX_SHIFT => func_return::FuncReturn::value(var::Var::Z(z << b).best_fit()),
+            _ => func_return::FuncReturn::func_error(format!("Shift by {} bits is too large, it can be at most {}!", bits, MAX_SHIFT)),
+        };
+    }
+
+    func_return::FuncReturn::func_error("Function needs an integer and a number of bits!".to_string())
+}
@@ -0,0 +1,30 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Shifts bits of an integer (first argument) to the right by a number of bits (second argument).
+/// Negative numbers stay negative, the result is rounded down.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::Z(z), var::Var::N(bits)) = (&args[0], &args[1]) {
+        return match usize::try_from(bits) {
+            Ok(b) => func_return::FuncReturn::value(var::Var::Z(z >> b).best_fit()),
+            Err(_e) => func_return::FuncReturn::func_error(format!("Shift by {} bits is too large!", bits)),
+        };
+    }
+
+    func_return::FuncReturn::func_error("Function needs an integer and a number of bits!".to_string())
+}
@@ -0,0 +1,33 @@
+use super::super::var;
+use num_traits::{ Zero, One };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::q(Zero::zero(), One::one()).unwrap(),
+                var::Var::d(Zero::zero(), 0).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns -1 for a negative number, 0 for zero and 1 for a positive number.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if args.len() != 1 {
+        return func_return::FuncReturn::func_error("Function needs exactly one number!".to_string());
+    }
+
+    let zero = var::Var::new(0);
+    let sign = match args[0].partial_cmp(&zero) {
+        Some(std::cmp::Ordering::Less) => -1,
+        Some(std::cmp::Ordering::Greater) => 1,
+        _ => 0,
+    };
+    func_return::FuncReturn::value(var::Var::new(sign))
+}
@@ -0,0 +1,56 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Unlimited(
+            vec!(
+                var::Var::a(Vec::new()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns items of a list (first argument) from a start index (second argument) up to
+/// but not including an end index (optional third argument). Without an end index the
+/// rest of the list is returned.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if args.len() < 2 || args.len() > 3 {
+        return func_return::FuncReturn::func_error("Function needs a list, a start index and optionally an end index!".to_string());
+    }
+
+    if let var::Var::A(list) = &args[0] {
+        let start = match index(&args[1], list.len()) {
+            Ok(i) => i,
+            Err(e) => return func_return::FuncReturn::func_error(e),
+        };
+        let end = match args.get(2).map(|end| index(end, list.len())) {
+            None => list.len(),
+            Some(Ok(i)) => i,
+            Some(Err(e)) => return func_return::FuncReturn::func_error(e),
+        };
+        if start > end {
+            return func_return::FuncReturn::func_error(format!("Start of a slice {} is after its end {}!", start, end));
+        }
+        return func_return::FuncReturn::value(var::Var::A(list[start..end].to_vec()));
+    }
+
+    func_return::FuncReturn::func_error("First argument has to be a list!".to_string())
+}
+
+/// Converts a value to an index of an item in a list with `len` items. The index can point
+/// right after the last item.
+fn index(var: &var::Var, len: usize) -> Result<usize, String> {
+    match super::at::index(var, len + 1) {
+        Ok(i) => Ok(i),
+        Err(_e) if var.get_n().is_ok() => {
+            Err(format!("Index {} is out of range of a list with {} items!", var.plain_string(), len))
+        },
+        Err(e) => Err(e),
+    }
+}
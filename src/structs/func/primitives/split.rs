@@ -0,0 +1,30 @@
+use super::super::var;
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+                var::Var::t(String::new()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Splits a text (first argument) at every occurrence of a separator (second argument) into
+/// a list of texts. An empty separator splits the text into characters.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let (var::Var::T(text), var::Var::T(separator)) = (&args[0], &args[1]) {
+        let parts: Vec<var::Var> = match separator.is_empty() {
+            true => text.chars().map(|c| var::Var::T(c.to_string())).collect(),
+            false => text.split(&separator[..]).map(|s| var::Var::T(s.to_string())).collect(),
+        };
+        return func_return::FuncReturn::value(var::Var::A(parts));
+    }
+
+    func_return::FuncReturn::func_error("Both arguments have to be text!".to_string())
+}
@@ -0,0 +1,48 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::t(String::new()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Returns characters of a text (first argument) from a start index (second argument) up to
+/// but not including an end index (third argument). Indexes count characters from zero.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::T(text) = &args[0] {
+        let chars: Vec<char> = text.chars().collect();
+        let (start, end) = match (index(&args[1], chars.len()), index(&args[2], chars.len())) {
+            (Ok(start), Ok(end)) => (start, end),
+            (Err(e), _) | (_, Err(e)) => return func_return::FuncReturn::func_error(e),
+        };
+        if start > end {
+            return func_return::FuncReturn::func_error(format!("Start of a substring {} is after its end {}!", start, end));
+        }
+        return func_return::FuncReturn::value(var::Var::t(chars[start..end].iter().collect()).unwrap());
+    }
+
+    func_return::FuncReturn::func_error("First argument has to be text!".to_string())
+}
+
+/// Converts a value to an index of a character in a text with `len` characters. The index
+/// can point right after the last character.
+pub fn index(var: &var::Var, len: usize) -> Result<usize, String> {
+    match super::at::index(var, len + 1) {
+        Ok(i) => Ok(i),
+        Err(_e) if var.get_n().is_ok() => {
+            Err(format!("Index {} is out of range of a text with {} characters!", var.plain_string(), len))
+        },
+        Err(e) => Err(e),
+    }
+}
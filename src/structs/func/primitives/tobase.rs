@@ -0,0 +1,30 @@
+use super::super::var;
+use num_traits::{ Zero };
+use super::super::func_return;
+use super::super::Func;
+use super::super::super::func;
+
+pub fn get_func() -> Func {
+    Func {
+        func: func::FuncBody::Primitive(op),
+        args: func::ArgSpec::Limited(
+            vec!(
+                var::Var::z(Zero::zero()).unwrap(),
+                var::Var::n(Zero::zero()).unwrap(),
+            )
+        ),
+    }
+}
+
+/// Writes an integer (first argument) in a base from 2 to 36 (second argument) as text.
+/// Digits above 9 are lowercase letters.
+pub fn op(args: &[var::Var]) -> func_return::FuncReturn {
+    if let var::Var::Z(z) = &args[0] {
+        return match super::frombase::base(&args[1]) {
+            Ok(b) => func_return::FuncReturn::value(var::Var::t(z.to_str_radix(b)).unwrap()),
+            Err(e) => func_return::FuncReturn::func_error(e),
+        };
+    }
+
+    func_return::FuncReturn::func_error("Function needs an integer and a base!".to_string())
+}
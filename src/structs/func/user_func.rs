@@ -0,0 +1,93 @@
+use colored::Colorize;
+use super::super::{ var, word, line };
+use super::{ Func, FuncBody, ArgSpec };
+use crate::parser::rtoken;
+use std::rc::Rc;
+use std::collections::HashMap;
+
+/// Function defined in runk source code with a block like this:
+///
+/// ```runk
+/// <fn Nat square Nat x
+///     ret (* $x $x)
+/// >
+/// ```
+#[derive(Clone)]
+pub struct UserFunc {
+    pub name: String,
+    /// Names of the parameters and variables representing their types.
+    pub params: Vec<(String, var::Var)>,
+    /// Variable representing the type of the returned value.
+    pub ret: var::Var,
+    /// Lines between the header and the closing ">".
+    pub body: Vec<line::Line>,
+    /// Lables declared in the body and indexes of their lines.
+    pub lables: HashMap<String, usize>,
+}
+
+impl UserFunc {
+    /// # Description
+    /// Creates a function from the words of its header and lines of its body.
+    ///
+    /// # Arguments
+    /// - `header`: Words of the header line, including the leading "<fn".
+    /// - `body`: Lines of the function's body without the closing ">".
+    ///
+    /// # Returns
+    /// - `Ok`: The new function.
+    /// - `Err`:
+    ///     - String: Description of the issue intended to be shown to the user.
+    ///     - Word: The problematic word.
+    pub fn new(header: &[word::Word], body: Vec<line::Line>) -> Result<UserFunc, (String, &word::Word)> {
+        if header.len() < 3 {
+            return Err(("Function needs a return type and a name!".to_string(), &header[header.len()-1]));
+        }
+
+        let ret = match &header[1].rtoken {
+            rtoken::Rtoken::DataType(dtype) => (**dtype).clone(),
+            _ => return Err((format!("Return type \"{}\" is invalid!", header[1].original.italic()), &header[1])),
+        };
+
+        let name = match &header[2].rtoken {
+            rtoken::Rtoken::Plain(name) => name.clone(),
+            _ => return Err((format!("Function name \"{}\" is invalid!", header[2].original.italic()), &header[2])),
+        };
+
+        let mut params = Vec::<(String, var::Var)>::new();
+        for pair in header[3..].chunks(2) {
+            let dtype = match &pair[0].rtoken {
+                rtoken::Rtoken::DataType(dtype) => (**dtype).clone(),
+                _ => return Err((format!("Parameter type \"{}\" is invalid!", pair[0].original.italic()), &pair[0])),
+            };
+            if pair.len() < 2 {
+                return Err(("Parameter is missing a name!".to_string(), &pair[0]));
+            }
+            let param_name = match &pair[1].rtoken {
+                rtoken::Rtoken::Plain(name) => name.clone(),
+                _ => return Err((format!("Parameter name \"{}\" is invalid!", pair[1].original.italic()), &pair[1])),
+            };
+            if params.iter().any(|(n, _)| *n == param_name) {
+                return Err((format!("Parameter \"{}\" is declared twice!", param_name.italic()), &pair[1]));
+            }
+            params.push((param_name, dtype));
+        }
+
+        Ok(UserFunc {
+            name,
+            params,
+            ret,
+            body,
+            lables: HashMap::new(),
+        })
+    }
+
+    /// Wraps the function so it can be stored among other functions. Arguments
+    /// are checked against the types of the parameters.
+    pub fn into_func(self) -> Func {
+        let args = self.params.iter().map(|(_, dtype)| dtype.clone()).collect();
+        Func {
+            func: FuncBody::User(Rc::new(self)),
+            args: ArgSpec::Limited(args),
+        }
+    }
+}
@@ -1,28 +1,39 @@
 use super::word;
+use crate::parser::ast;
+use crate::bytecode;
 use std::fmt;
+use std::rc::Rc;
+use std::cell::OnceCell;
 
-#[derive(Debug)]
-pub struct Line<'a> {
-    pub file_name: &'a str,
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub file_name: String,
     pub line_number: usize,
     pub content: Vec<word::Word>,
-    pub original: String,
+    /// What the line does, built from `content` by the parser.
+    pub statement: ast::Statement,
+    /// Compiled expression of the statement, created when the line is run for the first time.
+    pub chunk: OnceCell<bytecode::Chunk>,
+    /// Line as it appears in the file, shared with errors and debug output.
+    pub original: Rc<str>,
     pub is_last: bool,
 }
 
-impl Line<'_> {
-    pub fn new<'a>(file_name: &'a str, line_number: usize, is_last: bool) -> Line<'a> {
+impl Line {
+    pub fn new(file_name: &str, line_number: usize, is_last: bool) -> Line {
         Line {
-            file_name: file_name,
+            file_name: file_name.to_string(),
             line_number: line_number,
             content: Vec::new(),
-            original: String::new(),
+            statement: ast::Statement::Empty,
+            chunk: OnceCell::new(),
+            original: Rc::from(""),
             is_last: is_last,
         }
     }
 }
 
-impl fmt::Display for Line<'_> {
+impl fmt::Display for Line {
     fn fmt<'a>(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut string = String::new();
         for word in &self.content {
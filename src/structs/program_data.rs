@@ -1,42 +1,70 @@
 use std::collections::HashMap;
 
-use super::var;
+use super::{ var, slots, scopes, decimal };
 use super::func::{ self };
 
 const VAR_ERROR: &str = "ERROR";
+/// Calls of runk functions nested deeper than this fail. Keeps the stack of a debug build
+/// running on a main thread from overflowing.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 200;
 
 #[derive(Clone)]
 pub struct ProgramData {
-    /// Stores runk program's variables
-    pub vars: HashMap<String, var::Var>,
-    /// Stores runk program's functions. Currently only stores primitive functions.
-    pub funcs: HashMap<String, func::Func>,
+    /// Stores runk program's variables in nested scopes.
+    pub vars: scopes::Scopes,
+    /// Stores runk program's functions, both primitive and defined in runk source code.
+    pub funcs: slots::Slots<func::Func>,
     /// Stores lables of a runk program. For the purpouse of jumping to them.
     pub lables: HashMap<String, usize>,
     pub debug: bool,
     /// Register all lables from a program before execution.
     pub prescan_lables: bool,
+    /// Number of runk functions that are currently running.
+    pub call_depth: usize,
+    /// Largest allowed `call_depth`.
+    pub max_call_depth: usize,
+    /// Scale and rounding of decimal division. Installed on the thread only while the program runs.
+    pub decimal: decimal::Context,
 }
 
 impl ProgramData {
+    /// Creates data of a new runk program with primitive functions and special variables.
     pub fn new(enable_debug: bool) -> ProgramData {
-        ProgramData {
-            vars: HashMap::new(),
-            funcs: HashMap::new(),
+        let mut data = ProgramData {
+            vars: scopes::Scopes::new(),
+            funcs: slots::Slots::new(),
             lables: HashMap::new(),
             debug: enable_debug,
             prescan_lables: false,
-        }
+            call_depth: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            decimal: Default::default(),
+        };
+        data.add_primitive_functions();
+        data.add_special_variables();
+        data
     }
 
     pub fn add_special_variables(&mut self) {
-        self.vars.insert(VAR_ERROR.to_string(), var::Var::t(format!("")).unwrap());
+        self.vars.insert_global(VAR_ERROR, var::Var::t(String::new()).unwrap());
     }
 
     pub fn debug_vars_print(&self) {
         crate::color_print!("\nVariables:\n", blue italic);
-        for (key, val) in self.vars.iter() {
-            eprintln!("{}: {}", key, val);
+        // Scopes from the global one to the innermost one.
+        for depth in 0..=self.vars.depth() {
+            let kind = match depth {
+                0 => "global",
+                d if self.vars.is_function_scope(d) => "function",
+                _ => "block",
+            };
+            eprintln!("{}", format!("[{} {}]", depth, kind).italic());
+            for (key, val, constant) in self.vars.scope(depth) {
+                match constant {
+                    true => eprintln!("const {}: {}", key, val),
+                    false => eprintln!("{}: {}", key, val),
+                }
+            }
         }
     }
 
@@ -52,9 +80,20 @@ impl ProgramData {
         self.debug_funcs_print();
     }
 
+    /// Runs `f` with the decimal context of this program installed on the thread. Changes
+    /// made by the code are kept in `decimal` and the context of the thread is restored.
+    pub fn with_decimal_context<T>(&mut self, f: impl FnOnce(&mut ProgramData) -> T) -> T {
+        let outer = decimal::context();
+        decimal::set_context(self.decimal);
+        let result = f(self);
+        self.decimal = decimal::context();
+        decimal::set_context(outer);
+        result
+    }
+
     pub fn set_error(&mut self, string: String) -> String {
-        let old_string = self.vars.get(VAR_ERROR).unwrap().clone();
-        self.vars.insert(self::VAR_ERROR.to_string(), var::Var::t(string).unwrap());
+        let old_string = self.vars.get_global(VAR_ERROR).unwrap().clone();
+        self.vars.insert_global(VAR_ERROR, var::Var::t(string).unwrap());
 
         if let var::Var::T(text) = old_string {
             return text.clone();
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::error;
+use super::{ word, source_info };
+
+/// What went wrong when running runk code.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorKind {
+    /// Source code could not be parsed, like an unclosed bracket or a call without a function name.
+    Parse,
+    /// Tokens are not arranged in a valid way, like a block without its closing ">".
+    Syntax,
+    /// Code is valid but failed while running, like a call to a missing function.
+    Runtime,
+    /// The program asked to end with the given exit code by calling "exit".
+    Exit(i32),
+}
+
+/// Error that stopped a runk program. Carries everything needed to show the user
+/// where the problem is.
+#[derive(Clone, Debug)]
+pub struct RunkError {
+    pub kind: ErrorKind,
+    /// Description of the issue intended to be shown to the user.
+    pub message: String,
+    pub file_name: String,
+    pub line_number: usize,
+    /// Column of the problematic word if there is one.
+    pub column: Option<usize>,
+    /// The line as it appears in the file.
+    pub original: String,
+    /// The problematic word.
+    pub word: Option<Box<word::Word>>,
+}
+
+impl RunkError {
+    /// # Description
+    /// Creates an error related to a line of runk source code.
+    ///
+    /// # Arguments
+    /// - `kind`: Kind of the error.
+    /// - `message`: Description of the issue intended to be shown to the user.
+    /// - `info`: Source information of the line where the error happened.
+    /// - `opt_word`: The problematic word if there is one.
+    pub fn new(kind: ErrorKind,
+               message: String,
+               info: &source_info::SourceInfo,
+               opt_word: Option<&word::Word>) -> RunkError {
+        RunkError {
+            kind,
+            message,
+            file_name: info.file_name.to_string(),
+            line_number: info.line_number,
+            column: opt_word.map(|w| w.column),
+            original: info.original.to_string(),
+            word: opt_word.map(|w| Box::new(w.clone())),
+        }
+    }
+
+    /// Creates an error requesting the program to end with `code`.
+    pub fn exit(code: i32) -> RunkError {
+        RunkError {
+            kind: ErrorKind::Exit(code),
+            message: format!("Program exited with code {}.", code),
+            file_name: String::new(),
+            line_number: 0,
+            column: None,
+            original: String::new(),
+            word: None,
+        }
+    }
+}
+
+impl fmt::Display for RunkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "{}:{}:{}: {}", self.file_name, self.line_number, column, self.message),
+            None => write!(f, "{}:{}: {}", self.file_name, self.line_number, self.message),
+        }
+    }
+}
+
+impl error::Error for RunkError {}
+
+
+// ==========
+// Unit tests
+// ==========
+#[test]
+fn test_error_kinds() {
+    let mut interpreter = crate::Interpreter::new();
+    let error = interpreter.run_str("Nat a: 1\nNat b: (+ 1\n").unwrap_err();
+    assert_eq!((error.kind, error.line_number), (ErrorKind::Parse, 2));
+    let error = interpreter.run_str("ret 1\n").unwrap_err();
+    assert_eq!((error.kind, error.column), (ErrorKind::Syntax, Some(0)));
+    let error = interpreter.run_str("\nNat c: (nope 1)\n").unwrap_err();
+    assert_eq!((error.kind, error.line_number, error.column), (ErrorKind::Runtime, 2, Some(8)));
+    assert_eq!(error.word.as_ref().unwrap().original, "nope");
+    assert_eq!(error.original, "Nat c: (nope 1)");
+    assert_eq!(error.to_string(), format!("<string>:2:8: {}", error.message));
+    // Exit is an error too, so the host decides what happens.
+    let error = interpreter.run_str("(exit 4)\n(line 1)\n").unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Exit(4));
+    assert_eq!(error.message, "Program exited with code 4.");
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use super::var;
+
+/// Value of a variable together with the depth of the scope it was declared in.
+#[derive(Clone)]
+struct Binding {
+    depth: usize,
+    value: var::Var,
+    /// Where the variable was declared if it's a constant. Like: "file.runk:12"
+    constant: Option<String>,
+}
+
+/// Variables of a runk program organized into nested scopes. The first scope is global,
+/// every block and every function call opens a new one that is closed when it ends.
+///
+/// A variable declared in a scope shadows variables with the same name from the outer
+/// ones until the scope is closed. Code of a function sees the scopes it opened and the
+/// global scope, variables of its caller are hidden from it.
+///
+/// Like functions, variables are referred to by slots assigned to their names.
+#[derive(Clone)]
+pub struct Scopes {
+    indexes: HashMap<String, usize>,
+    names: Vec<String>,
+    /// Declarations of every slot from the outermost one to the innermost one.
+    bindings: Vec<Vec<Binding>>,
+    /// Slots declared in each open scope, starting with the global one.
+    scopes: Vec<Vec<usize>>,
+    /// Depths of scopes opened by function calls.
+    frames: Vec<usize>,
+}
+
+impl Scopes {
+    /// Creates variables with only the global scope open.
+    pub fn new() -> Scopes {
+        Scopes {
+            indexes: HashMap::new(),
+            names: Vec::new(),
+            bindings: Vec::new(),
+            scopes: vec!(Vec::new()),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Returns the slot of `name`, a new one is assigned if it doesn't have one yet.
+    pub fn slot(&mut self, name: &str) -> usize {
+        if let Some(slot) = self.indexes.get(name) {
+            return *slot;
+        }
+
+        let slot = self.names.len();
+        self.indexes.insert(name.to_string(), slot);
+        self.names.push(name.to_string());
+        self.bindings.push(Vec::new());
+        slot
+    }
+
+    /// Returns the slot of `name` if it has one.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.indexes.get(name).copied()
+    }
+
+    /// Name of the variable in `slot`.
+    pub fn name(&self, slot: usize) -> &str {
+        &self.names[slot]
+    }
+
+    /// Depth of the innermost scope, the global scope is zero.
+    pub fn depth(&self) -> usize {
+        self.scopes.len() - 1
+    }
+
+    /// Returns the index into `bindings[slot]` of the declaration visible from the innermost scope.
+    fn visible(&self, slot: usize) -> Option<usize> {
+        let bindings = self.bindings.get(slot)?;
+        let frame = self.frames.last().copied().unwrap_or(0);
+        match bindings.last() {
+            Some(b) if b.depth >= frame => Some(bindings.len() - 1),
+            // Global declaration is always the first one.
+            _ => bindings.first().filter(|b| b.depth == 0).map(|_| 0),
+        }
+    }
+
+    /// Value of the variable in `slot` visible from the innermost scope.
+    pub fn get_slot(&self, slot: usize) -> Option<&var::Var> {
+        let i = self.visible(slot)?;
+        Some(&self.bindings[slot][i].value)
+    }
+
+    /// Value of the variable in `slot` visible from the innermost scope.
+    pub fn get_slot_mut(&mut self, slot: usize) -> Option<&mut var::Var> {
+        let i = self.visible(slot)?;
+        Some(&mut self.bindings[slot][i].value)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&var::Var> {
+        self.get_slot(self.find(name)?)
+    }
+
+    /// Value of the variable declared in the global scope, even if it is shadowed.
+    pub fn get_global(&self, name: &str) -> Option<&var::Var> {
+        self.bindings[self.find(name)?].first().filter(|b| b.depth == 0).map(|b| &b.value)
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Where the variable in `slot` visible from the innermost scope was declared if it's a constant.
+    pub fn constant(&self, slot: usize) -> Option<&str> {
+        let i = self.visible(slot)?;
+        self.bindings[slot][i].constant.as_deref()
+    }
+
+    /// Value of the variable in `slot` if it was declared in the innermost scope.
+    pub fn get_declared(&self, slot: usize) -> Option<&var::Var> {
+        self.bindings[slot].last().filter(|b| b.depth == self.depth()).map(|b| &b.value)
+    }
+
+    /// Declares a variable in the innermost scope or replaces its value if it's already declared there.
+    pub fn declare(&mut self, slot: usize, value: var::Var) {
+        self.bind(slot, value, None);
+    }
+
+    /// Declares a constant in the innermost scope. `location` is where it was declared.
+    pub fn declare_constant(&mut self, slot: usize, value: var::Var, location: String) {
+        self.bind(slot, value, Some(location));
+    }
+
+    fn bind(&mut self, slot: usize, value: var::Var, constant: Option<String>) {
+        let depth = self.depth();
+        match self.bindings[slot].last_mut() {
+            Some(b) if b.depth == depth => {
+                b.value = value;
+                b.constant = constant;
+            },
+            _ => {
+                self.bindings[slot].push(Binding { depth, value, constant });
+                self.scopes[depth].push(slot);
+            },
+        }
+    }
+
+    /// Declares a variable in the innermost scope or replaces its value if it's already declared there.
+    pub fn insert(&mut self, name: String, value: var::Var) {
+        let slot = self.slot(&name);
+        self.declare(slot, value);
+    }
+
+    /// Declares a variable in the global scope or replaces its value if it's already declared there.
+    pub fn insert_global(&mut self, name: &str, value: var::Var) {
+        let slot = self.slot(name);
+        match self.bindings[slot].first_mut() {
+            Some(b) if b.depth == 0 => b.value = value,
+            _ => {
+                self.bindings[slot].insert(0, Binding { depth: 0, value, constant: None });
+                self.scopes[0].push(slot);
+            },
+        }
+    }
+
+    /// Opens a new innermost scope.
+    pub fn enter_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    /// Closes the innermost scope, its variables are gone. The global scope stays open.
+    pub fn exit_scope(&mut self) {
+        if self.scopes.len() == 1 {
+            return;
+        }
+        for slot in self.scopes.pop().unwrap() {
+            self.bindings[slot].pop();
+        }
+    }
+
+    /// Opens a scope for a function call that hides variables of the caller.
+    pub fn enter_function(&mut self) {
+        self.enter_scope();
+        self.frames.push(self.depth());
+    }
+
+    /// Closes the scope of the current function call and all scopes opened inside of it.
+    pub fn exit_function(&mut self) {
+        let frame = self.frames.pop().unwrap_or(1);
+        while self.depth() >= frame {
+            self.exit_scope();
+        }
+    }
+
+    /// Closes every scope except for the global one.
+    pub fn reset(&mut self) {
+        while self.depth() > 0 {
+            self.exit_scope();
+        }
+        self.frames.clear();
+    }
+
+    /// Names and values of variables declared in the scope at `depth` and whether they are constants.
+    pub fn scope(&self, depth: usize) -> impl Iterator<Item = (&str, &var::Var, bool)> {
+        self.scopes[depth].iter().map(move |slot| {
+            let binding = self.bindings[*slot].iter().rev().find(|b| b.depth == depth).unwrap();
+            (&self.names[*slot][..], &binding.value, binding.constant.is_some())
+        })
+    }
+
+    /// Whether the scope at `depth` was opened by a function call.
+    pub fn is_function_scope(&self, depth: usize) -> bool {
+        self.frames.contains(&depth)
+    }
+}
+
+impl Default for Scopes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
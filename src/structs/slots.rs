@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+/// Named values stored in a vector. Names are resolved to indexes (slots) once when
+/// the code is compiled, so running it doesn't need to look them up. A slot stays
+/// assigned to its name even when the value is removed.
+#[derive(Clone)]
+pub struct Slots<T> {
+    indexes: HashMap<String, usize>,
+    names: Vec<String>,
+    values: Vec<Option<T>>,
+}
+
+impl<T: Clone> Slots<T> {
+    pub fn new() -> Slots<T> {
+        Slots {
+            indexes: HashMap::new(),
+            names: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Returns the slot of `name`, a new one is assigned if it doesn't have one yet.
+    pub fn slot(&mut self, name: &str) -> usize {
+        if let Some(slot) = self.indexes.get(name) {
+            return *slot;
+        }
+
+        let slot = self.names.len();
+        self.indexes.insert(name.to_string(), slot);
+        self.names.push(name.to_string());
+        self.values.push(None);
+        slot
+    }
+
+    /// Returns the slot of `name` if it has one.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.indexes.get(name).copied()
+    }
+
+    /// Name of the value stored in `slot`.
+    pub fn name(&self, slot: usize) -> &str {
+        &self.names[slot]
+    }
+
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.get_slot(self.find(name)?)
+    }
+
+    pub fn get_slot(&self, slot: usize) -> Option<&T> {
+        self.values.get(slot)?.as_ref()
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Stores `value` under `name`, replacing the old value.
+    pub fn insert(&mut self, name: String, value: T) {
+        let slot = self.slot(&name);
+        self.values[slot] = Some(value);
+    }
+
+    /// Stores `value` in `slot`, replacing the old value.
+    pub fn insert_slot(&mut self, slot: usize, value: T) {
+        self.values[slot] = Some(value);
+    }
+
+    /// Names and values of everything that's stored in the order the names got their slots.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.names.iter().zip(&self.values)
+            .filter_map(|(name, value)| value.as_ref().map(|v| (&name[..], v)))
+    }
+}
+
+impl<T: Clone> Default for Slots<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
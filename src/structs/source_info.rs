@@ -1,16 +1,18 @@
+use std::rc::Rc;
+
 #[derive(Clone)]
 pub struct SourceInfo<'a> {
     pub line_number: usize,
     pub file_name:   &'a str,
-    pub original:    String,
+    pub original:    Rc<str>,
 }
 
 impl SourceInfo<'_> {
-    pub fn new<'a>(line_number: usize, file_name: &'a str, original: String) -> SourceInfo<'a> {
+    pub fn new<'a>(line_number: usize, file_name: &'a str, original: impl Into<Rc<str>>) -> SourceInfo<'a> {
         SourceInfo {
             line_number: line_number,
             file_name: file_name,
-            original,
+            original: original.into(),
         }
     }
 }
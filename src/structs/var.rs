@@ -1,25 +1,37 @@
 use std::fmt;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::ops::{ Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign };
 use num_bigint::{ BigInt, ToBigInt };
-use num_traits::Zero;
+use num_traits::{ Zero, One, Signed };
+use num_integer::Integer;
 use std::result::Result;
 use colored::Colorize;
 use super::word;
+use super::decimal;
 use crate::parser::rtoken;
 
 
 /// Variants of this enum represent different variable types supported
 /// in runk.
-#[derive(Clone, PartialOrd)]
+#[derive(Clone)]
 pub enum Var {
     /// Natural number type
     N(BigInt),
     /// Integer type
     Z(BigInt),
+    /// Rational number type, a numerator and a positive denominator with no common divisor.
+    Q(BigInt, BigInt),
+    /// Decimal number type, its digits without the decimal point and the number of digits after it.
+    D(BigInt, u32),
     /// Text (string) type
     T(String),
     /// Lable type (used for jumping)
     L(String),
+    /// List of values of any type
+    A(Vec<Var>),
+    /// Map of values of any type under text keys, ordered by the keys
+    M(BTreeMap<String, Var>),
 }
 
 // ------------
@@ -45,6 +57,23 @@ impl Var {
         Ok(Var::N(value))
     }
 
+    /// Creates a new variable of variant Q with the value of numerator / denominator.
+    pub fn q(numerator: BigInt, denominator: BigInt) -> Result<Var, String> {
+        if denominator.is_zero() {
+            return Err(format!("Denominator of \"{}/{}\" is zero.", numerator, denominator));
+        }
+
+        // Keeping the fraction normalized, so equal values look the same.
+        let divisor = numerator.gcd(&denominator);
+        let sign: BigInt = if denominator.is_negative() { -BigInt::one() } else { BigInt::one() };
+        Ok(Var::Q(&sign * numerator / &divisor, sign * denominator / divisor))
+    }
+
+    /// Creates a new variable of variant D with the value of digits / 10^scale.
+    pub fn d(digits: BigInt, scale: u32) -> Result<Var, String> {
+        Ok(Var::D(digits, scale))
+    }
+
     pub fn t(value: String) -> Result<Var, String> {
         Ok(Var::T(value))
     }
@@ -53,6 +82,14 @@ impl Var {
         Ok(Var::L(value))
     }
 
+    pub fn a(value: Vec<Var>) -> Result<Var, String> {
+        Ok(Var::A(value))
+    }
+
+    pub fn m(value: BTreeMap<String, Var>) -> Result<Var, String> {
+        Ok(Var::M(value))
+    }
+
     /// Creates a text var from a text token:
     pub fn text_from_word(word: &word::Word) -> Result<Var, String> {
         if let rtoken::Rtoken::TextLiteral(value) = &word.rtoken {
@@ -63,13 +100,105 @@ impl Var {
 
     /// Creates a numeric variable from string.
     pub fn num_from_str(string: &str) -> Result<Var, String> {
-        return match string.parse::<BigInt>() {
-            Ok(i) => {
-                Ok(Var::new(i))
+        match Var::num_literal(string) {
+            Some(Ok(var)) => Ok(var),
+            Some(Err((e, _offset, _length))) => Err(e),
+            None => Err(format!("Cannot convert \"{}\" to a number.", string.italic())),
+        }
+    }
+
+    /// # Description
+    /// Parses a number written like in runk code. Integers can be decimal, hexadecimal (`0xFF`),
+    /// octal (`0o17`) or binary (`0b1010`). A decimal point makes a decimal number (`12.50`) and
+    /// a slash a fraction (`3/4`). Digits can be separated by underscores (`1_000_000`), all
+    /// numbers can begin with a sign.
+    ///
+    /// # Returns
+    /// - `None`: The string doesn't begin with a digit, so it isn't a number.
+    /// - `Some(Err)`:
+    ///     - String: Description of the issue intended to be shown to the user.
+    ///     - usize: Index of the first char of the problematic part.
+    ///     - usize: Length of the problematic part in chars.
+    pub fn num_literal(string: &str) -> Option<Result<Var, (String, usize, usize)>> {
+        let chars: Vec<char> = string.chars().collect();
+        let sign = match chars.first() {
+            Some('-') | Some('+') => 1,
+            _ => 0,
+        };
+        let negative = chars.first() == Some(&'-');
+        if !chars.get(sign).is_some_and(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let (radix, name) = match (chars[sign], chars.get(sign+1)) {
+            ('0', Some('x')) => (16, "hexadecimal"),
+            ('0', Some('o')) => (8, "octal"),
+            ('0', Some('b')) => (2, "binary"),
+            _ => (10, "decimal"),
+        };
+        let digits_start = if radix == 10 { sign } else { sign + 2 };
+
+        // Digits before and after the decimal point or the slash, with the index of the separator.
+        let mut groups = vec!(String::new());
+        let mut separator: Option<(char, usize)> = None;
+        for (i, c) in chars.iter().enumerate().skip(digits_start) {
+            match c {
+                '_' => {
+                    let is_digit = |j: usize| chars.get(j).is_some_and(|c| c.is_digit(radix));
+                    if !is_digit(i-1) || !is_digit(i+1) {
+                        return Some(Err(("Underscore in a number has to be between two digits!".to_string(), i, 1)));
+                    }
+                },
+                '.' | '/' if radix == 10 && separator.is_none() => {
+                    separator = Some((*c, i));
+                    groups.push(String::new());
+                },
+                c if c.is_digit(radix) => groups.last_mut().unwrap().push(*c),
+                c if radix != 10 && c.is_ascii_alphanumeric() => {
+                    return Some(Err((format!("Digit \"{}\" is not valid in a {} number!", c, name), i, 1)));
+                },
+                c => return Some(Err((format!("Unexpected character \"{}\" in a number!", c), i, 1))),
+            }
+        }
+
+        if groups[0].is_empty() {
+            return Some(Err((format!("Number \"{}\" has no digits!", string), 0, chars.len())));
+        }
+        let parse = |digits: &str| {
+            let value = BigInt::parse_bytes(digits.as_bytes(), radix).unwrap();
+            if negative { -value } else { value }
+        };
+        let var = match separator {
+            None => Var::new(parse(&groups[0])),
+            Some((c, i)) if groups[1].is_empty() => {
+                return Some(Err((format!("Expected digits after \"{}\" in a number!", c), i, 1)));
             },
-            Err(_e) => {
-                Err(format!("Cannot convert \"{}\" to a number.", string.italic()))
+            Some(('.', _i)) => Var::D(parse(&groups.concat()), groups[1].len() as u32),
+            Some((_c, i)) => {
+                let denominator = BigInt::parse_bytes(groups[1].as_bytes(), radix).unwrap();
+                match Var::q(parse(&groups[0]), denominator) {
+                    Ok(v) => v,
+                    Err(e) => return Some(Err((e, i+1, chars.len() - i - 1))),
+                }
             },
+        };
+        Some(Ok(var))
+    }
+
+    /// Creates a variable of variant Q from a fraction like "3/4".
+    pub fn rat_from_str(string: &str) -> Result<Var, String> {
+        match Var::num_from_str(string)? {
+            var @ Var::Q(..) => Ok(var),
+            _ => Err(format!("Cannot convert \"{}\" to a rational number.", string.italic())),
+        }
+    }
+
+    /// Creates a variable of variant D from a decimal number like "12.50". Its scale is
+    /// the number of digits after the decimal point.
+    pub fn dec_from_str(string: &str) -> Result<Var, String> {
+        match Var::num_from_str(string)? {
+            var @ Var::D(..) => Ok(var),
+            _ => Err(format!("Cannot convert \"{}\" to a decimal number.", string.italic())),
         }
     }
 
@@ -106,10 +235,7 @@ impl Var {
     pub fn new(value: impl num_bigint::ToBigInt) -> Var {
         let bigint = match ToBigInt::to_bigint(&value) {
             Some(b) => b,
-            None => {
-                eprintln!("Error during bigint conversion. This was never supposed to happen lol.");
-                std::process::exit(1);
-            },
+            None => panic!("Error during bigint conversion. This was never supposed to happen lol."),
         };
 
         if Var::can_be_n(&bigint) {
@@ -164,6 +290,8 @@ impl Var {
                     },
                 }
             },
+            Var::Q(_n, _d) => matches!(other, Var::Q(_, _)),
+            Var::D(_n, _s) => matches!(other, Var::D(_, _)),
             Var::L(_l) => {
                 match other {
                     Var::L(_o_l) => {
@@ -184,6 +312,8 @@ impl Var {
                     },
                 }
             },
+            Var::A(_a) => matches!(other, Var::A(_)),
+            Var::M(_m) => matches!(other, Var::M(_)),
         }
     }
 
@@ -199,9 +329,13 @@ impl Var {
     /// Returns a variable containing self's value as variant Z.
     pub fn get_z(&self) -> Result<Self, String> {
         // Getting original value.
-        let value: &BigInt = match self {
-            Var::N(n) => n,
+        let value: BigInt = match self {
+            Var::N(n) => n.clone(),
             Var::Z(_z) => return Result::Ok(self.clone()),
+            Var::Q(..) | Var::D(..) => match self.whole() {
+                Some(n) => n,
+                None => return Result::Err(format!("Cannot convert a fraction \"{}\" to an integer", self.plain_string().italic())),
+            },
             _ => return Result::Err(format!("Cannot convert to natural number")),
         };
 
@@ -209,15 +343,19 @@ impl Var {
         // In this case none.
 
         // Converting
-        Result::Ok(Var::Z(value.clone()))
+        Result::Ok(Var::Z(value))
     }
 
     /// Returns a variable containing self's value as variant N.
     pub fn get_n(&self) -> Result<Self, String> {
 
-        let value: &BigInt = match self {
+        let value: BigInt = match self {
             Var::N(_n) => return Result::Ok(self.clone()),
-            Var::Z(z) => &z,
+            Var::Z(z) => z.clone(),
+            Var::Q(..) | Var::D(..) => match self.whole() {
+                Some(n) => n,
+                None => return Result::Err(format!("Cannot convert a fraction \"{}\" to natural number", self.plain_string().italic())),
+            },
             _ => return Result::Err(format!("Cannot convert to natural number")),
         };
 
@@ -227,7 +365,71 @@ impl Var {
         }
 
         // Converting
-        Result::Ok(Var::N(value.clone()))
+        Result::Ok(Var::N(value))
+    }
+
+
+    /// Returns a variable containing self's value as variant Q.
+    pub fn get_q(&self) -> Result<Self, String> {
+        match self {
+            Var::N(n) | Var::Z(n) => Ok(Var::Q(n.clone(), One::one())),
+            Var::Q(_n, _d) => Ok(self.clone()),
+            Var::D(n, s) => Var::q(n.clone(), decimal::pow10(*s)),
+            _ => Err(format!("Cannot convert variable \"{}\" to a rational number.", &self)),
+        }
+    }
+
+    /// Returns a variable containing self's value as variant D. Fractions are rounded
+    /// using the scale and rounding set by the `scale` primitive.
+    pub fn get_d(&self) -> Result<Self, String> {
+        match self {
+            Var::N(n) | Var::Z(n) => Ok(Var::D(n.clone(), 0)),
+            Var::D(_n, _s) => Ok(self.clone()),
+            // Fractions like 3/4 have an exact decimal form and keep it, others are rounded.
+            Var::Q(_n, d) => match decimal::exact_scale(d) {
+                Some(scale) => self.round(scale, decimal::Rounding::HalfEven),
+                None => {
+                    let context = decimal::context();
+                    self.round(context.scale, context.rounding)
+                },
+            },
+            _ => Err(format!("Cannot convert variable \"{}\" to a decimal number.", &self)),
+        }
+    }
+
+    /// Returns a decimal number with `scale` digits after the decimal point closest to
+    /// self's value, ties and the direction are decided by `rounding`.
+    pub fn round(&self, scale: u32, rounding: decimal::Rounding) -> Result<Self, String> {
+        match self.fraction() {
+            Some((n, d)) => Var::d(rounding.divide(&(n * decimal::pow10(scale)), &d), scale),
+            None => Err(format!("Cannot round variable \"{}\", it's not a number.", &self)),
+        }
+    }
+
+    /// Numerator and positive denominator of a number.
+    fn fraction(&self) -> Option<(BigInt, BigInt)> {
+        match self {
+            Var::N(n) | Var::Z(n) => Some((n.clone(), One::one())),
+            Var::Q(n, d) => Some((n.clone(), d.clone())),
+            Var::D(n, s) => Some((n.clone(), decimal::pow10(*s))),
+            _ => None,
+        }
+    }
+
+    /// Value of a number if it's whole.
+    fn whole(&self) -> Option<BigInt> {
+        let (n, d) = self.fraction()?;
+        let (quotient, remainder) = n.div_rem(&d);
+        remainder.is_zero().then_some(quotient)
+    }
+
+    /// Digits and scale of a whole or a decimal number.
+    fn decimal_parts(&self) -> Option<(BigInt, u32)> {
+        match self {
+            Var::N(n) | Var::Z(n) => Some((n.clone(), 0)),
+            Var::D(n, s) => Some((n.clone(), *s)),
+            _ => None,
+        }
     }
 
 
@@ -257,6 +459,32 @@ impl Var {
     }
 
 
+    /// Returns a variable containing self's value as variant A (list).
+    pub fn get_a(&self) -> Result<Self, String> {
+        match self {
+            Var::A(_a) => {
+                Ok(self.clone())
+            },
+            _ => {
+                Err(format!("Cannot convert variable \"{}\" to a list.", &self))
+            },
+        }
+    }
+
+
+    /// Returns a variable containing self's value as variant M (map).
+    pub fn get_m(&self) -> Result<Self, String> {
+        match self {
+            Var::M(_m) => {
+                Ok(self.clone())
+            },
+            _ => {
+                Err(format!("Cannot convert variable \"{}\" to a map.", &self))
+            },
+        }
+    }
+
+
     /// Converts any var (if possible) to the N variant.
     pub fn to_n(&mut self) -> Result<Self, String> {
         let var = self.get_n()?;
@@ -276,9 +504,12 @@ impl Var {
         *other = match other {
             Var::N(_n) => self.get_n()?,
             Var::Z(_z) => self.get_z()?,
+            Var::Q(_n, _d) => self.get_q()?,
+            Var::D(_n, _s) => self.get_d()?,
             Var::T(_t) => self.get_t()?,
             Var::L(_l) => self.get_l()?,
-            _ => other.clone(),
+            Var::A(_a) => self.get_a()?,
+            Var::M(_m) => self.get_m()?,
         };
 
         Result::Ok(other)
@@ -289,7 +520,18 @@ impl Var {
     /// ## Example
     /// - Positive integer will allways change to Var::N
     /// - Negative integer will always change to Var::Z
+    /// - Fraction with denominator 1 will change to one of the above
+    /// - Decimal number keeps its type, even if it's whole
     pub fn best_fit(&mut self) -> Self {
+        if let Var::D(_n, _s) = self {
+            return self.clone();
+        }
+        if let Var::Q(_n, d) = self {
+            if !d.is_one() {
+                return self.clone();
+            }
+            *self = self.get_z().unwrap();
+        }
         let result = self.to_n();
         if result.is_ok() {
             return result.unwrap().clone();
@@ -313,12 +555,50 @@ impl Var {
             Var::Z(z) => {
                 format!("{}", z)
             },
+            Var::Q(n, d) if d.is_one() => {
+                format!("{}", n)
+            },
+            Var::Q(n, d) => {
+                format!("{}/{}", n, d)
+            },
+            Var::D(n, 0) => {
+                format!("{}", n)
+            },
+            Var::D(n, s) => {
+                // Leading zeros so there is a digit before the decimal point.
+                let digits = format!("{:0>width$}", n.abs(), width = *s as usize + 1);
+                let (whole, decimals) = digits.split_at(digits.len() - *s as usize);
+                let sign = if n.is_negative() { "-" } else { "" };
+                format!("{}{}.{}", sign, whole, decimals)
+            },
             Var::T(t) => {
                 format!("{}", t)
             },
             Var::L(_l) => {
                 format!("")
             },
+            Var::A(a) => {
+                let items: Vec<String> = a.iter().map(|v| v.item_string()).collect();
+                format!("{{{}}}", items.join(" "))
+            },
+            Var::M(m) if m.is_empty() => {
+                "{:}".to_string()
+            },
+            Var::M(m) => {
+                let items: Vec<String> = m.iter()
+                    .map(|(k, v)| format!("{}: {}", Var::T(k.clone()).item_string(), v.item_string()))
+                    .collect();
+                format!("{{{}}}", items.join(" "))
+            },
+        }
+    }
+
+    /// Value written the way it would be as an item of a list literal.
+    fn item_string(&self) -> String {
+        match self {
+            Var::T(t) => format!("\"{}\"", t.replace('\\', "\\\\").replace('"', "\\\"")),
+            Var::L(l) => format!("!{}", l),
+            _ => self.plain_string(),
         }
     }
 }
@@ -330,13 +610,12 @@ impl Var {
 
 impl PartialEq for Var {
     fn eq(&self, other: &Self) -> bool {
+        // Numbers are equal if their values are, regardless of their types.
+        if let (Some((num_s, den_s)), Some((num_o, den_o))) = (self.fraction(), other.fraction()) {
+            return num_s * den_o == num_o * den_s;
+        }
         match self {
-            Var::Z(num_s) | Var::N(num_s) => {
-                match other {
-                    Var::Z(num_o) | Var::N(num_o) => num_s == num_o,
-                    _ => false,
-                }
-            },
+            Var::Z(_) | Var::N(_) | Var::Q(..) | Var::D(..) => false,
             Var::T(text_s) => {
                 match other {
                     Var::T(text_o) => *text_s == *text_o,
@@ -349,8 +628,34 @@ impl PartialEq for Var {
                     _ => false,
                 }
             },
+            Var::A(list_s) => {
+                match other {
+                    Var::A(list_o) => *list_s == *list_o,
+                    _ => false,
+                }
+            },
+            Var::M(map_s) => {
+                match other {
+                    Var::M(map_o) => *map_s == *map_o,
+                    _ => false,
+                }
+            },
+        }
+    }
+}
 
-            _ => false,
+impl PartialOrd for Var {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if let (Some((num_s, den_s)), Some((num_o, den_o))) = (self.fraction(), other.fraction()) {
+            // Denominators are positive.
+            return (num_s * den_o).partial_cmp(&(num_o * den_s));
+        }
+        match (self, other) {
+            (Var::T(text_s), Var::T(text_o)) => text_s.partial_cmp(text_o),
+            (Var::L(lable_s), Var::L(lable_o)) => lable_s.partial_cmp(lable_o),
+            (Var::A(list_s), Var::A(list_o)) => list_s.partial_cmp(list_o),
+            (Var::M(map_s), Var::M(map_o)) => map_s.partial_cmp(map_o),
+            _ => None,
         }
     }
 }
@@ -364,12 +669,24 @@ impl fmt::Display for Var {
             Var::Z(z) => {
                 write!(f, "Int {}", z)
             },
+            Var::Q(_n, _d) => {
+                write!(f, "Rat {}", self.plain_string())
+            },
+            Var::D(_n, _s) => {
+                write!(f, "Dec {}", self.plain_string())
+            },
             Var::T(s) => {
                 write!(f, "Txt {}", s)
             },
             Var::L(s) => {
                 write!(f, "Lab {}", s)
             },
+            Var::A(_a) => {
+                write!(f, "Lst {}", self.plain_string())
+            },
+            Var::M(_m) => {
+                write!(f, "Map {}", self.plain_string())
+            },
         }
     }
 }
@@ -384,22 +701,54 @@ impl fmt::Debug for Var {
             Var::Z(z) => {
                 write!(f, "Int {}", z)
             },
+            Var::Q(_n, _d) => {
+                write!(f, "Rat {}", self.plain_string())
+            },
+            Var::D(_n, _s) => {
+                write!(f, "Dec {}", self.plain_string())
+            },
             Var::T(s) => {
                 write!(f, "Txt {}", s)
             },
             Var::L(s) => {
                 write!(f, "Lab {}", s)
             },
+            Var::A(_a) => {
+                write!(f, "Lst {}", self.plain_string())
+            },
+            Var::M(_m) => {
+                write!(f, "Map {}", self.plain_string())
+            },
         }
     }
 }
 
 /// # Generating arithmetic operators.
+///
+/// `$rat` computes the result for fractions, it takes the numerator and denominator
+/// of both operands and returns those of the result. `$dec` does the same for decimal
+/// numbers with their digits and scales.
 macro_rules! ops_generate {
-    ($trait1: ident, $fn1: ident, $trait2: ident, $fn2: ident) => {
+    ($trait1: ident, $fn1: ident, $trait2: ident, $fn2: ident, $rat: expr, $dec: expr) => {
         impl $trait1 for Var {
             type Output = Self;
             fn $fn1(self, other: Var) -> Self::Output {
+                if let (Var::Q(..), _) | (_, Var::Q(..)) = (&self, &other) {
+                    let ((n, d), (o_n, o_d)) = match (self.fraction(), other.fraction()) {
+                        (Some(s), Some(o)) => (s, o),
+                        _ => panic!("Incompatible variants."),
+                    };
+                    let (n, d): (BigInt, BigInt) = $rat(n, d, o_n, o_d);
+                    return Var::q(n, d).expect("Division by zero.");
+                }
+                if let (Var::D(..), _) | (_, Var::D(..)) = (&self, &other) {
+                    let ((n, s), (o_n, o_s)) = match (self.decimal_parts(), other.decimal_parts()) {
+                        (Some(s), Some(o)) => (s, o),
+                        _ => panic!("Incompatible variants."),
+                    };
+                    let (n, s): (BigInt, u32) = $dec(n, s, o_n, o_s);
+                    return Var::D(n, s);
+                }
                 return match self {
                     Var::N(n) => {
                         match other {
@@ -437,10 +786,30 @@ macro_rules! ops_generate {
 
     }
 }
-ops_generate!(Add, add, AddAssign, add_assign);
-ops_generate!(Sub, sub, SubAssign, sub_assign);
-ops_generate!(Mul, mul, MulAssign, mul_assign);
-ops_generate!(Div, div, DivAssign, div_assign);
+ops_generate!(Add, add, AddAssign, add_assign, |n, d: BigInt, o_n, o_d: BigInt| (n * &o_d + o_n * &d, d * o_d),
+              |n: BigInt, s: u32, o_n: BigInt, o_s: u32| {
+                  let scale = s.max(o_s);
+                  (n * decimal::pow10(scale - s) + o_n * decimal::pow10(scale - o_s), scale)
+              });
+ops_generate!(Sub, sub, SubAssign, sub_assign, |n, d: BigInt, o_n, o_d: BigInt| (n * &o_d - o_n * &d, d * o_d),
+              |n: BigInt, s: u32, o_n: BigInt, o_s: u32| {
+                  let scale = s.max(o_s);
+                  (n * decimal::pow10(scale - s) - o_n * decimal::pow10(scale - o_s), scale)
+              });
+ops_generate!(Mul, mul, MulAssign, mul_assign, |n: BigInt, d: BigInt, o_n, o_d| (n * o_n, d * o_d),
+              |n: BigInt, s: u32, o_n: BigInt, o_s: u32| (n * o_n, s + o_s));
+ops_generate!(Div, div, DivAssign, div_assign, |n: BigInt, d: BigInt, o_n, o_d| (n * o_d, d * o_n),
+              |n: BigInt, s: u32, o_n: BigInt, o_s: u32| {
+                  // Quotient is rounded to the scale set by the `scale` primitive.
+                  let context = decimal::context();
+                  let numerator = n * decimal::pow10(o_s + context.scale);
+                  let denominator = o_n * decimal::pow10(s);
+                  let quotient = match denominator.is_negative() {
+                      true => context.rounding.divide(&-numerator, &-denominator),
+                      false => context.rounding.divide(&numerator, &denominator),
+                  };
+                  (quotient, context.scale)
+              });
 
 
 // ==========
@@ -482,3 +851,87 @@ fn test_fit_into() {
     // Testing that var3's variant remained unchanged.
     assert_eq!(var3.eq_type(&Var::z(ToBigInt::to_bigint(&50).unwrap()).unwrap()), false);
 }
+
+#[test]
+fn test_list() {
+    let list = Var::a(vec!(Var::new(1), Var::z(ToBigInt::to_bigint(&-2).unwrap()).unwrap(), Var::t("a \"b\"".to_string()).unwrap())).unwrap();
+    assert_eq!(list.plain_string(), "{1 -2 \"a \\\"b\\\"\"}");
+    assert_eq!(Var::a(vec!(list.clone())).unwrap().plain_string(), format!("{{{}}}", list.plain_string()));
+
+    // Numbers are equal regardless of their types.
+    assert!(list == Var::a(vec!(Var::z(ToBigInt::to_bigint(&1).unwrap()).unwrap(), Var::new(-2), Var::t("a \"b\"".to_string()).unwrap())).unwrap());
+    assert!(list != Var::a(vec!(Var::new(1))).unwrap());
+    assert!(list.fit_into(&mut Var::new(0)).is_err());
+    assert!(list.eq_type(&Var::a(Vec::new()).unwrap()));
+}
+
+#[test]
+fn test_map() {
+    let mut items = BTreeMap::new();
+    items.insert("b".to_string(), Var::new(2));
+    items.insert("a".to_string(), Var::a(vec!(Var::new(1))).unwrap());
+    let map = Var::m(items).unwrap();
+    // Keys are always ordered.
+    assert_eq!(map.plain_string(), "{\"a\": {1} \"b\": 2}");
+    assert_eq!(Var::m(BTreeMap::new()).unwrap().plain_string(), "{:}");
+    assert!(map != Var::m(BTreeMap::new()).unwrap());
+    assert!(map.eq_type(&Var::m(BTreeMap::new()).unwrap()));
+    assert!(!map.eq_type(&Var::a(Vec::new()).unwrap()));
+}
+
+#[test]
+fn test_rat() {
+    let half = Var::rat_from_str("-2/4").unwrap();
+    assert_eq!(half.plain_string(), "-1/2");
+    assert_eq!(Var::dec_from_str("-0.5").unwrap().get_q().unwrap().plain_string(), "-1/2");
+    assert_eq!(Var::q(ToBigInt::to_bigint(&3).unwrap(), ToBigInt::to_bigint(&-6).unwrap()).unwrap(), half);
+    assert!(Var::rat_from_str("1/0").is_err());
+    assert!(Var::rat_from_str("1/").is_err() && Var::rat_from_str("0.5").is_err() && Var::rat_from_str("/").is_err());
+
+    // Whole fractions are equal to integers and turn into them.
+    let mut two = Var::rat_from_str("4/2").unwrap();
+    assert_eq!(two, Var::new(2));
+    assert!(two.best_fit().eq_type(&Var::new(2)));
+    assert!(half < Var::new(0) && half > Var::rat_from_str("-2/3").unwrap());
+    assert_eq!((half.clone() + Var::new(1)).plain_string(), "1/2");
+    assert_eq!((Var::new(1) / half.clone()).plain_string(), "-2");
+    assert!(half.fit_into(&mut Var::new(0)).is_err());
+}
+
+#[test]
+fn test_num_literal() {
+    let value = |s: &str| Var::num_literal(s).unwrap().unwrap().plain_string();
+    assert_eq!(value("0xFF"), "255");
+    assert_eq!(value("-0o17"), "-15");
+    assert_eq!(value("0b1010_1010"), "170");
+    assert_eq!(value("1_000_000"), "1000000");
+    assert_eq!(value("1_000.000_5"), "1000.0005");
+    assert_eq!(value("+3/1_2"), "1/4");
+    assert!(Var::num_literal("x1").is_none() && Var::num_literal("-").is_none());
+    assert!(Var::num_literal("").is_none() && Var::num_from_str("").is_err());
+    assert!(Var::rat_from_str("  ".trim()).is_err() && Var::dec_from_str("".trim()).is_err());
+
+    let error = |s: &str| Var::num_literal(s).unwrap().unwrap_err();
+    assert_eq!(error("1__0").1, 1);
+    assert_eq!(error("0x1g").1, 3);
+    assert_eq!(error("12.").1, 2);
+    assert_eq!(error("0b").2, 2);
+    assert_eq!(error("0x1.5").1, 3);
+}
+
+#[test]
+fn test_dec() {
+    let price = Var::dec_from_str("12.50").unwrap();
+    assert_eq!(price.plain_string(), "12.50");
+    assert_eq!(Var::dec_from_str("-0.05").unwrap().plain_string(), "-0.05");
+    assert_eq!((price.clone() * Var::new(3)).plain_string(), "37.50");
+    assert_eq!((price.clone() - Var::dec_from_str("20.125").unwrap()).plain_string(), "-7.625");
+    assert_eq!(price, Var::rat_from_str("25/2").unwrap());
+
+    // Whole decimals stay decimals but fit into integers.
+    let mut two = Var::dec_from_str("2.00").unwrap();
+    assert!(two.best_fit().eq_type(&price));
+    assert!(two.fit_into(&mut Var::new(0)).is_ok());
+    assert_eq!(Var::rat_from_str("-1/8").unwrap().round(2, decimal::Rounding::HalfEven).unwrap().plain_string(), "-0.12");
+    assert_eq!(Var::rat_from_str("-1/8").unwrap().round(2, decimal::Rounding::HalfUp).unwrap().plain_string(), "-0.13");
+}
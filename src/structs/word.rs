@@ -1,7 +1,7 @@
 use std::fmt;
 use crate::parser::rtoken;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Word {
     /// Runk token this word represents.
     pub rtoken: rtoken::Rtoken,